@@ -45,6 +45,18 @@ fn bench_json(c: &mut Criterion) {
         }
     });
 
+    c.bench_function("json_chumsky_zero_copy_dispatched", {
+        use ::chumsky::prelude::*;
+        let json = chumsky_zero_copy::json_dispatched::<EmptyErr>();
+        move |b| {
+            b.iter(|| {
+                black_box(json.parse(black_box(JSON)))
+                    .into_result()
+                    .unwrap()
+            })
+        }
+    });
+
     c.bench_function("json_chumsky_zero_copy_check", {
         use ::chumsky::prelude::*;
         let json = chumsky_zero_copy::json::<EmptyErr>();
@@ -69,6 +81,18 @@ fn bench_json(c: &mut Criterion) {
         }
     });
 
+    c.bench_function("json_chumsky_zero_copy_rich_dispatched", {
+        use ::chumsky::prelude::*;
+        let json = chumsky_zero_copy::json_dispatched::<Rich<u8>>();
+        move |b| {
+            b.iter(|| {
+                black_box(json.parse(black_box(JSON)))
+                    .into_result()
+                    .unwrap()
+            })
+        }
+    });
+
     c.bench_function("json_chumsky_zero_copy_check_rich", {
         use ::chumsky::prelude::*;
         let json = chumsky_zero_copy::json::<Rich<u8>>();
@@ -115,68 +139,81 @@ mod chumsky_zero_copy {
     use super::JsonZero;
     use std::str;
 
+    // Allows the same grammar to be benchmarked with and without `Choice::dispatched`
+    macro_rules! json {
+        ($($dispatched:ident)?) => {
+            recursive(|value| {
+                let digits = one_of(b'0'..=b'9').repeated();
+
+                let int = one_of(b'1'..=b'9')
+                    .then(one_of(b'0'..=b'9').repeated())
+                    .ignored()
+                    .or(just(b'0').ignored())
+                    .ignored();
+
+                let frac = just(b'.').then(digits.clone());
+
+                let exp = one_of(b"eE")
+                    .then(one_of(b"+-").or_not())
+                    .then(digits.clone());
+
+                let number = just(b'-')
+                    .or_not()
+                    .then(int)
+                    .then(frac.or_not())
+                    .then(exp.or_not())
+                    .map_slice(|bytes| str::from_utf8(bytes).unwrap().parse().unwrap())
+                    .boxed();
+
+                let escape = just(b'\\').then_ignore(one_of(b"\\/\"bfnrt"));
+
+                let string = none_of(b"\\\"")
+                    .or(escape)
+                    .repeated()
+                    .slice()
+                    .delimited_by(just(b'"'), just(b'"'))
+                    .boxed();
+
+                let array = value
+                    .clone()
+                    .separated_by(just(b','))
+                    .collect()
+                    .padded()
+                    .delimited_by(just(b'['), just(b']'))
+                    .boxed();
+
+                let member = string.clone().then_ignore(just(b':').padded()).then(value);
+                let object = member
+                    .clone()
+                    .separated_by(just(b',').padded())
+                    .collect()
+                    .padded()
+                    .delimited_by(just(b'{'), just(b'}'))
+                    .boxed();
+
+                choice((
+                    just(b"null").to(JsonZero::Null),
+                    just(b"true").to(JsonZero::Bool(true)),
+                    just(b"false").to(JsonZero::Bool(false)),
+                    number.map(JsonZero::Num),
+                    string.map(JsonZero::Str),
+                    array.map(JsonZero::Array),
+                    object.map(JsonZero::Object),
+                ))
+                $(.$dispatched())?
+                .padded()
+            })
+        };
+    }
+
     pub fn json<'a, E: Error<'a, &'a [u8]> + 'a>(
     ) -> impl Parser<'a, &'a [u8], JsonZero<'a>, extra::Err<E>> {
-        recursive(|value| {
-            let digits = one_of(b'0'..=b'9').repeated();
-
-            let int = one_of(b'1'..=b'9')
-                .then(one_of(b'0'..=b'9').repeated())
-                .ignored()
-                .or(just(b'0').ignored())
-                .ignored();
-
-            let frac = just(b'.').then(digits.clone());
-
-            let exp = one_of(b"eE")
-                .then(one_of(b"+-").or_not())
-                .then(digits.clone());
-
-            let number = just(b'-')
-                .or_not()
-                .then(int)
-                .then(frac.or_not())
-                .then(exp.or_not())
-                .map_slice(|bytes| str::from_utf8(bytes).unwrap().parse().unwrap())
-                .boxed();
-
-            let escape = just(b'\\').then_ignore(one_of(b"\\/\"bfnrt"));
-
-            let string = none_of(b"\\\"")
-                .or(escape)
-                .repeated()
-                .slice()
-                .delimited_by(just(b'"'), just(b'"'))
-                .boxed();
-
-            let array = value
-                .clone()
-                .separated_by(just(b','))
-                .collect()
-                .padded()
-                .delimited_by(just(b'['), just(b']'))
-                .boxed();
-
-            let member = string.clone().then_ignore(just(b':').padded()).then(value);
-            let object = member
-                .clone()
-                .separated_by(just(b',').padded())
-                .collect()
-                .padded()
-                .delimited_by(just(b'{'), just(b'}'))
-                .boxed();
-
-            choice((
-                just(b"null").to(JsonZero::Null),
-                just(b"true").to(JsonZero::Bool(true)),
-                just(b"false").to(JsonZero::Bool(false)),
-                number.map(JsonZero::Num),
-                string.map(JsonZero::Str),
-                array.map(JsonZero::Array),
-                object.map(JsonZero::Object),
-            ))
-            .padded()
-        })
+        json!()
+    }
+
+    pub fn json_dispatched<'a, E: Error<'a, &'a [u8]> + 'a>(
+    ) -> impl Parser<'a, &'a [u8], JsonZero<'a>, extra::Err<E>> {
+        json!(dispatched)
     }
 }
 
@@ -33,6 +33,8 @@ fn bench_choice(c: &mut Criterion) {
         just('Z'),
     ));
 
+    let alphabet_dispatched = alphabet_choice.dispatched();
+
     let mut group = c.benchmark_group("choice");
 
     group.bench_function(BenchmarkId::new("choice::<(A..Z)>", "A"), |b| {
@@ -58,6 +60,30 @@ fn bench_choice(c: &mut Criterion) {
                 .is_err());
         })
     });
+
+    group.bench_function(BenchmarkId::new("choice::<(A..Z)>::dispatched", "A"), |b| {
+        b.iter(|| {
+            black_box(alphabet_dispatched.parse(black_box("A")))
+                .into_result()
+                .unwrap();
+        })
+    });
+
+    group.bench_function(BenchmarkId::new("choice::<(A..Z)>::dispatched", "Z"), |b| {
+        b.iter(|| {
+            black_box(alphabet_dispatched.parse(black_box("Z")))
+                .into_result()
+                .unwrap();
+        })
+    });
+
+    group.bench_function(BenchmarkId::new("choice::<(A..Z)>::dispatched", "0"), |b| {
+        b.iter(|| {
+            assert!(black_box(alphabet_dispatched.parse(black_box("0")))
+                .into_result()
+                .is_err());
+        })
+    });
 }
 
 fn bench_or(c: &mut Criterion) {
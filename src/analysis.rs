@@ -0,0 +1,537 @@
+//! Static analysis of parsers, for finding problems with grammars before they show up at runtime.
+//!
+//! See [`Parser::analyze`] for more information.
+
+use super::*;
+
+/// The maximum number of times a recursive parser will be re-analysed while waiting for its summary to settle.
+const MAX_ITERATIONS: usize = 16;
+
+/// The maximum number of tokens that will be tracked in the set of tokens that a pattern can begin with.
+const MAX_FIRST: usize = 256;
+
+/// A problem with a grammar, found by [`Parser::analyze`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Problem {
+    /// A repeating pattern (such as [`Parser::repeated`]) was given an item that can succeed without consuming any
+    /// input, meaning that it can never make progress.
+    NullableRepetition {
+        /// The type of the repeating parser.
+        parser: &'static str,
+        /// Where the repeating parser was created (only available in debug builds).
+        location: Option<Location<'static>>,
+    },
+    /// An alternative of a [`choice`] (or [`Parser::or`]) can never be reached, because any input that it matches is
+    /// also matched by an earlier alternative.
+    ShadowedAlternative {
+        /// The index of the unreachable alternative.
+        index: usize,
+        /// The type of the unreachable alternative.
+        parser: &'static str,
+    },
+    /// A [`Recursive`] parser can recurse into itself without consuming any input, meaning that parsing will never
+    /// terminate.
+    LeftRecursion,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NullableRepetition { parser, location } => {
+                write!(
+                    f,
+                    "repeating parser '{}' may never make progress because its item can match without consuming input",
+                    parser
+                )?;
+                if let Some(location) = location {
+                    write!(f, " (at {})", location)?;
+                }
+                Ok(())
+            }
+            Self::ShadowedAlternative { index, parser } => write!(
+                f,
+                "alternative {} ('{}') can never match because an earlier alternative always matches first",
+                index, parser
+            ),
+            Self::LeftRecursion => write!(
+                f,
+                "recursive parser can recurse into itself without consuming input"
+            ),
+        }
+    }
+}
+
+/// A summary of the inputs that a pattern accepts, used internally by [`Parser::analyze`].
+#[doc(hidden)]
+#[derive(Clone, PartialEq)]
+pub struct Summary<T> {
+    // Whether the pattern can succeed without consuming input, or `None` if this is not known
+    pub(crate) nullable: Option<bool>,
+    // The tokens that can begin a non-empty match of the pattern, or `None` if they are not known
+    pub(crate) first: Option<Vec<T>>,
+    // The only sequence of tokens that the pattern accepts, if there is only one
+    pub(crate) literal: Option<Vec<T>>,
+    // A sequence of tokens that every input accepted by the pattern begins with
+    pub(crate) prefix: Vec<T>,
+    // Sequences of tokens that, if the input begins with one of them, guarantee that the pattern will succeed
+    pub(crate) sure: Vec<Vec<T>>,
+    // Whether the pattern, when the next token is not in `first`, fails with exactly the error 'expected one of
+    // `first`' at that token (and so doesn't need to be tried to know the error it would produce)
+    pub(crate) predictable: bool,
+}
+
+impl<T: Clone + PartialEq> Summary<T> {
+    /// A pattern about which nothing is known, not even whether it can succeed without consuming input.
+    pub(crate) fn opaque() -> Self {
+        Self {
+            nullable: None,
+            first: None,
+            literal: None,
+            prefix: Vec::new(),
+            sure: Vec::new(),
+            predictable: false,
+        }
+    }
+
+    /// A pattern that never succeeds, used as a starting point when analysing recursive parsers.
+    pub(crate) fn never() -> Self {
+        Self {
+            nullable: Some(false),
+            first: Some(Vec::new()),
+            predictable: true,
+            ..Self::opaque()
+        }
+    }
+
+    /// A pattern that always succeeds without consuming input.
+    pub(crate) fn empty() -> Self {
+        Self::literal(Vec::new())
+    }
+
+    /// A pattern that accepts a single token from the given set, or any token if the set is not known.
+    pub(crate) fn token(first: Option<Vec<T>>) -> Self {
+        match first.filter(|first| first.len() <= MAX_FIRST) {
+            Some(first) if first.len() == 1 => Self::literal(first),
+            first => Self {
+                nullable: Some(false),
+                first,
+                predictable: true,
+                ..Self::opaque()
+            },
+        }
+    }
+
+    /// A pattern that accepts only the given sequence of tokens.
+    pub(crate) fn literal(seq: Vec<T>) -> Self {
+        Self {
+            nullable: Some(seq.is_empty()),
+            first: Some(seq.first().cloned().into_iter().collect()),
+            literal: Some(seq.clone()),
+            prefix: seq.clone(),
+            sure: vec![seq],
+            predictable: true,
+        }
+    }
+
+    /// A pattern that looks ahead without consuming input, but may fail.
+    pub(crate) fn lookahead(first: Option<Vec<T>>) -> Self {
+        Self {
+            nullable: Some(true),
+            first,
+            ..Self::opaque()
+        }
+    }
+
+    /// Whether the pattern always succeeds.
+    pub(crate) fn infallible(&self) -> bool {
+        self.sure.iter().any(|s| s.is_empty())
+    }
+
+    /// The same pattern, but which may fail for reasons not reflected in its structure (such as a filter).
+    pub(crate) fn restricted(self) -> Self {
+        Self {
+            literal: None,
+            sure: Vec::new(),
+            ..self
+        }
+    }
+
+    /// The same pattern, but whose errors are replaced or altered (such as by a label), and so can't be predicted from
+    /// the tokens that it begins with.
+    pub(crate) fn relabelled(self) -> Self {
+        Self {
+            predictable: false,
+            ..self
+        }
+    }
+
+    /// The same pattern, but which may recover from errors by skipping over input that it would not otherwise accept.
+    pub(crate) fn recovering(self) -> Self {
+        Self {
+            first: None,
+            ..self
+        }
+    }
+
+    /// The same pattern, but which only matches if the pattern `other` (which must consume input) also matches at the
+    /// same position.
+    pub(crate) fn and(self, other: Self) -> Self {
+        let first = match (self.first, other.first) {
+            (Some(a), Some(b)) => Some(a.into_iter().filter(|tok| b.contains(tok)).collect()),
+            (a, b) => a.or(b),
+        };
+        Self {
+            first,
+            predictable: false,
+            ..self
+        }
+    }
+
+    /// The same pattern, but which may also succeed without consuming input.
+    pub(crate) fn or_empty(self) -> Self {
+        self.or(Self::empty())
+    }
+
+    /// The pattern `self` followed by the pattern `other`.
+    pub(crate) fn then(self, other: Self) -> Self {
+        let sure = match &self.literal {
+            Some(literal) => other
+                .sure
+                .iter()
+                .map(|s| literal.iter().chain(s).cloned().collect())
+                .collect(),
+            None if other.infallible() => self.sure.clone(),
+            None => Vec::new(),
+        };
+        let prefix = match &self.literal {
+            Some(literal) => literal.iter().chain(&other.prefix).cloned().collect(),
+            None => self.prefix,
+        };
+        Self {
+            nullable: match (self.nullable, other.nullable) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            first: if self.nullable == Some(false) {
+                self.first
+            } else {
+                union(self.first, other.first)
+            },
+            literal: match (self.literal, other.literal) {
+                (Some(mut a), Some(b)) => {
+                    a.extend(b);
+                    Some(a)
+                }
+                _ => None,
+            },
+            prefix,
+            sure,
+            predictable: self.predictable && (self.nullable == Some(false) || other.predictable),
+        }
+    }
+
+    /// Either the pattern `self` or the pattern `other`, trying `self` first.
+    pub(crate) fn or(self, other: Self) -> Self {
+        let prefix_len = self
+            .prefix
+            .iter()
+            .zip(&other.prefix)
+            .take_while(|(a, b)| a == b)
+            .count();
+        let mut sure = self.sure;
+        sure.extend(other.sure);
+        Self {
+            nullable: match (self.nullable, other.nullable) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            first: union(self.first, other.first),
+            literal: match (self.literal, other.literal) {
+                (Some(a), Some(b)) if a == b => Some(a),
+                _ => None,
+            },
+            prefix: self.prefix.into_iter().take(prefix_len).collect(),
+            sure,
+            predictable: self.predictable && other.predictable,
+        }
+    }
+
+    /// The pattern `self`, repeated at least `at_least` times.
+    pub(crate) fn repeated(self, at_least: usize) -> Self {
+        if at_least == 0 {
+            self.restricted().or_empty()
+        } else {
+            Self {
+                prefix: self.prefix.clone(),
+                ..self.restricted()
+            }
+        }
+    }
+}
+
+fn union<T: PartialEq>(a: Option<Vec<T>>, b: Option<Vec<T>>) -> Option<Vec<T>> {
+    let (mut a, b) = (a?, b?);
+    for tok in b {
+        if !a.contains(&tok) {
+            a.push(tok);
+        }
+    }
+    // Very large sets aren't worth tracking
+    if a.len() <= MAX_FIRST {
+        Some(a)
+    } else {
+        None
+    }
+}
+
+/// The state of an analysis, used internally by [`Parser::analyze`].
+#[doc(hidden)]
+pub struct Analyzer<T> {
+    problems: Vec<Problem>,
+    // Incremented whenever analysis moves past a pattern that must consume input
+    guards: usize,
+    // Recursive parsers currently being analysed, along with the value of `guards` when they were entered
+    stack: Vec<(usize, usize)>,
+    summaries: HashMap<usize, Summary<T>>,
+}
+
+impl<T: Clone + PartialEq> Analyzer<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            problems: Vec::new(),
+            guards: 0,
+            stack: Vec::new(),
+            summaries: HashMap::default(),
+        }
+    }
+
+    pub(crate) fn into_problems(self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        for problem in self.problems {
+            // Recursive parsers may be analysed several times, so the same problem may be found more than once
+            if !problems.contains(&problem) {
+                problems.push(problem);
+            }
+        }
+        problems
+    }
+
+    pub(crate) fn report(&mut self, problem: Problem) {
+        self.problems.push(problem);
+    }
+
+    /// Analyse the pattern produced by `then`, which follows the pattern `first`.
+    pub(crate) fn after(
+        &mut self,
+        first: &Summary<T>,
+        then: impl FnOnce(&mut Self) -> Summary<T>,
+    ) -> Summary<T> {
+        // Recursion is only unguarded if no input has necessarily been consumed since the recursive parser was entered
+        // (patterns that are not known to be nullable are given the benefit of the doubt)
+        let guarded = first.nullable != Some(true);
+        self.guards += guarded as usize;
+        let then = then(self);
+        self.guards -= guarded as usize;
+        then
+    }
+
+    /// Analyse the pattern `first` followed by the pattern produced by `then`.
+    pub(crate) fn then(
+        &mut self,
+        first: Summary<T>,
+        then: impl FnOnce(&mut Self) -> Summary<T>,
+    ) -> Summary<T> {
+        let then = self.after(&first, then);
+        first.then(then)
+    }
+
+    /// Analyse a series of alternatives, reporting any that are shadowed by earlier ones.
+    pub(crate) fn alternatives(
+        &mut self,
+        alts: impl IntoIterator<Item = (Summary<T>, &'static str)>,
+    ) -> Summary<T> {
+        let mut summary: Option<Summary<T>> = None;
+        for (index, (alt, parser)) in alts.into_iter().enumerate() {
+            summary = Some(match summary {
+                Some(summary) => {
+                    if summary.sure.iter().any(|s| alt.prefix.starts_with(s)) {
+                        self.report(Problem::ShadowedAlternative { index, parser });
+                    }
+                    summary.or(alt)
+                }
+                None => alt,
+            });
+        }
+        summary.unwrap_or_else(Summary::never)
+    }
+
+    /// Analyse a repeating pattern, reporting it if its item can match without consuming input.
+    pub(crate) fn repeated(
+        &mut self,
+        item: Summary<T>,
+        at_least: usize,
+        parser: &'static str,
+        location: Option<Location<'static>>,
+    ) -> Summary<T> {
+        if item.nullable == Some(true) {
+            self.report(Problem::NullableRepetition { parser, location });
+        }
+        item.repeated(at_least)
+    }
+
+    /// Analyse a recursive parser, identified by `id`, by repeatedly analysing its definition until its summary
+    /// settles.
+    pub(crate) fn recursive(
+        &mut self,
+        id: usize,
+        f: impl Fn(&mut Self) -> Summary<T>,
+    ) -> Summary<T> {
+        if let Some((_, guards)) = self.stack.iter().find(|(other, _)| *other == id) {
+            if *guards == self.guards {
+                self.report(Problem::LeftRecursion);
+            }
+            return self
+                .summaries
+                .get(&id)
+                .cloned()
+                .unwrap_or_else(Summary::never);
+        } else if self.stack.is_empty() {
+            // Summaries are only final once every enclosing recursive parser has been analysed
+            if let Some(summary) = self.summaries.get(&id) {
+                return summary.clone();
+            }
+        }
+
+        self.stack.push((id, self.guards));
+        let mut summary = Summary::never();
+        let mut settled = false;
+        for _ in 0..MAX_ITERATIONS {
+            self.summaries.insert(id, summary.clone());
+            let next = f(self);
+            settled = next == summary;
+            if settled {
+                break;
+            }
+            summary = next;
+        }
+        self.stack.pop();
+        // A summary that hasn't settled might not cover every input that the parser accepts
+        if !settled {
+            summary = Summary::opaque();
+        }
+        self.summaries.insert(id, summary.clone());
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::Problem;
+
+    #[test]
+    fn nullable_repetition() {
+        let parser = just::<_, &str, extra::Default>('a')
+            .or_not()
+            .repeated()
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            parser.analyze().as_slice(),
+            [Problem::NullableRepetition { .. }],
+        ));
+
+        let parser = just::<_, &str, extra::Default>('a')
+            .repeated()
+            .at_least(1)
+            .collect::<Vec<_>>()
+            .separated_by(just(','))
+            .collect::<Vec<_>>();
+        assert_eq!(parser.analyze(), Vec::new());
+
+        // A nullable item is fine if the separator always consumes input
+        let parser = just::<_, &str, extra::Default>('a')
+            .or_not()
+            .separated_by(just(','))
+            .collect::<Vec<_>>();
+        assert_eq!(parser.analyze(), Vec::new());
+
+        // Combinators that only change the context are seen through
+        let parser = just::<_, &str, extra::Default>('a')
+            .or_not()
+            .with_ctx(())
+            .repeated()
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            Parser::<_, Vec<_>, extra::Default>::analyze(&parser).as_slice(),
+            [Problem::NullableRepetition { .. }],
+        ));
+
+        // Parsers whose structure is unknown might or might not consume input, so aren't reported
+        let parser =
+            custom::<_, &str, _, extra::Default>(|inp| inp.next().ok_or_else(Default::default))
+                .repeated()
+                .collect::<Vec<_>>();
+        assert_eq!(parser.analyze(), Vec::new());
+    }
+
+    #[test]
+    fn shadowed_alternative() {
+        let parser = just::<_, &str, extra::Default>("a").or(just("ab"));
+        assert!(matches!(
+            parser.analyze().as_slice(),
+            [Problem::ShadowedAlternative { index: 1, .. }],
+        ));
+
+        // Longer alternatives first are fine
+        let parser = just::<_, &str, extra::Default>("ab").or(just("a"));
+        assert_eq!(parser.analyze(), Vec::new());
+
+        // An alternative that always succeeds shadows everything after it
+        let parser = choice::<_>((
+            just::<_, &str, extra::Default>('a').to(1),
+            empty().to(2),
+            just('b').to(3),
+        ));
+        assert!(matches!(
+            parser.analyze().as_slice(),
+            [Problem::ShadowedAlternative { index: 2, .. }],
+        ));
+
+        // Filters might reject input, so don't shadow later alternatives
+        let parser = any::<&str, extra::Default>()
+            .filter(|c: &char| c.is_ascii_digit())
+            .or(just('a'));
+        assert_eq!(parser.analyze(), Vec::new());
+    }
+
+    #[test]
+    fn left_recursion() {
+        let expr = recursive::<&str, _, extra::Default, _, _>(|expr| {
+            expr.then_ignore(just('+'))
+                .then(just('x'))
+                .to(())
+                .or(just('x').to(()))
+        });
+        assert_eq!(expr.analyze(), vec![Problem::LeftRecursion]);
+
+        // Indirect left recursion, through an optional prefix
+        let expr = recursive::<&str, _, extra::Default, _, _>(|expr| {
+            just('-')
+                .or_not()
+                .ignore_then(expr.delimited_by(empty(), just('!')))
+                .or(just('x').to(()))
+        });
+        assert_eq!(expr.analyze(), vec![Problem::LeftRecursion]);
+
+        let expr = recursive::<&str, _, extra::Default, _, _>(|expr| {
+            expr.delimited_by(just('('), just(')'))
+                .or(just('x').to(()))
+                .padded()
+        });
+        assert_eq!(expr.analyze(), Vec::new());
+    }
+}
@@ -13,6 +13,37 @@ where
         (*self).go::<M>(inp)
     }
 
+    fn name(&self) -> Option<&'static str> {
+        (*self).name()
+    }
+
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        (*self).summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        (*self).generate(gen)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate_where(
+        &self,
+        gen: &mut Generator<'_, I::Token>,
+        f: &dyn Fn(&O) -> bool,
+    ) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        (*self).generate_where(gen, f)
+    }
+
     go_extra!(O);
 }
 
@@ -6,6 +6,10 @@
 //! when accessed through their respective methods on [`Parser`].
 
 use super::*;
+use crate::{
+    input::{Marker, Rebased},
+    primitive::End,
+};
 
 /// The type of a lazy parser.
 pub type Lazy<'a, A, I, E> =
@@ -18,6 +22,12 @@ pub struct Configure<A, F> {
     pub(crate) cfg: F,
 }
 
+impl<A: fmt::Debug, F> fmt::Debug for Configure<A, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Configure").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A, F> ParserSealed<'a, I, O, E> for Configure<A, F>
 where
     A: ConfigParser<'a, I, O, E>,
@@ -34,6 +44,15 @@ where
         self.parser.go_cfg::<M>(inp, cfg)
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        // The configuration may change what the parser accepts, so only problems within it can be found
+        self.parser.summarize(cx);
+        Summary::opaque()
+    }
+
     go_extra!(O);
 }
 
@@ -56,6 +75,12 @@ impl<A: Clone, F: Clone, OA> Clone for IterConfigure<A, F, OA> {
     }
 }
 
+impl<A: fmt::Debug, F, OA> fmt::Debug for IterConfigure<A, F, OA> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IterConfigure").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, OA, E, A, F> ParserSealed<'a, I, (), E> for IterConfigure<A, F, OA>
 where
     A: ConfigIterParser<'a, I, OA, E>,
@@ -75,6 +100,15 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        // The configuration may change what the parser accepts, so only problems within it can be found
+        self.parser.summarize_iter(cx);
+        Summary::opaque()
+    }
+
     go_extra!(());
 }
 
@@ -108,6 +142,13 @@ where
     ) -> IPResult<M, O> {
         self.parser.next_cfg(inp, &mut state.0, &state.1)
     }
+
+    fn summarize_iter(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        ParserSealed::<I, (), E>::summarize(self, cx)
+    }
 }
 
 /// See [`ConfigIterParser::try_configure`]
@@ -129,6 +170,14 @@ impl<A: Clone, F: Clone, O> Clone for TryIterConfigure<A, F, O> {
     }
 }
 
+impl<A: fmt::Debug, F, O> fmt::Debug for TryIterConfigure<A, F, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TryIterConfigure")
+            .field(&self.parser)
+            .finish()
+    }
+}
+
 impl<'a, I, OA, E, A, F> ParserSealed<'a, I, (), E> for TryIterConfigure<A, F, OA>
 where
     A: ConfigIterParser<'a, I, OA, E>,
@@ -148,6 +197,15 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        // The configuration may change what the parser accepts, so only problems within it can be found
+        self.parser.summarize_iter(cx);
+        Summary::opaque()
+    }
+
     go_extra!(());
 }
 
@@ -183,6 +241,13 @@ where
     ) -> IPResult<M, O> {
         self.parser.next_cfg(inp, &mut state.0, &state.1)
     }
+
+    fn summarize_iter(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        ParserSealed::<I, (), E>::summarize(self, cx)
+    }
 }
 
 /// See [`Parser::map_slice`].
@@ -223,6 +288,18 @@ where
     }
 }
 
+impl<'a, A, I, O, E, F, U> fmt::Debug for MapSlice<'a, A, I, O, E, F, U>
+where
+    I: SliceInput<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E> + fmt::Debug,
+    F: Fn(I::Slice) -> U,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("MapSlice").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A, F, U> ParserSealed<'a, I, U, E> for MapSlice<'a, A, I, O, E, F, U>
 where
     I: SliceInput<'a>,
@@ -239,6 +316,21 @@ where
         Ok(M::bind(|| (self.mapper)(inp.slice_inner(before..after))))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(U);
 }
 
@@ -259,6 +351,12 @@ impl<A: Clone, O> Clone for Slice<A, O> {
     }
 }
 
+impl<A: fmt::Debug, O> fmt::Debug for Slice<A, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Slice").field(&self.parser).finish()
+    }
+}
+
 impl<'a, A, I, O, E> ParserSealed<'a, I, I::Slice, E> for Slice<A, O>
 where
     A: Parser<'a, I, O, E>,
@@ -277,6 +375,21 @@ where
         Ok(M::bind(|| inp.slice_inner(before..after)))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(I::Slice);
 }
 
@@ -296,6 +409,12 @@ impl<A: Clone, F: Clone> Clone for Filter<A, F> {
     }
 }
 
+impl<A: fmt::Debug, F> fmt::Debug for Filter<A, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Filter").field(&self.parser).finish()
+    }
+}
+
 impl<'a, A, I, O, E, F> ParserSealed<'a, I, O, E> for Filter<A, F>
 where
     I: Input<'a>,
@@ -317,6 +436,34 @@ where
         })
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx).restricted()
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate_where(gen, &|out| (self.filter)(out))
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate_where(
+        &self,
+        gen: &mut Generator<'_, I::Token>,
+        f: &dyn Fn(&O) -> bool,
+    ) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser
+            .generate_where(gen, &|out| (self.filter)(out) && f(out))
+    }
+
     go_extra!(O);
 }
 
@@ -339,6 +486,12 @@ impl<A: Clone, OA, F: Clone> Clone for Map<A, OA, F> {
     }
 }
 
+impl<A: fmt::Debug, OA, F> fmt::Debug for Map<A, OA, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Map").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A, OA, F> ParserSealed<'a, I, O, E> for Map<A, OA, F>
 where
     I: Input<'a>,
@@ -352,6 +505,21 @@ where
         Ok(M::map(out, &self.mapper))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -386,6 +554,21 @@ where
             Err(()) => Err(()),
         }
     }
+
+    fn summarize_iter(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize_iter(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate_iter(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate_iter(gen)
+    }
 }
 
 /// See [`Parser::map_with_span`].
@@ -407,6 +590,12 @@ impl<A: Clone, OA, F: Clone> Clone for MapWithSpan<A, OA, F> {
     }
 }
 
+impl<A: fmt::Debug, OA, F> fmt::Debug for MapWithSpan<A, OA, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("MapWithSpan").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A, OA, F> ParserSealed<'a, I, O, E> for MapWithSpan<A, OA, F>
 where
     I: Input<'a>,
@@ -424,6 +613,21 @@ where
         }))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -446,6 +650,12 @@ impl<A: Clone, OA, F: Clone> Clone for MapWithState<A, OA, F> {
     }
 }
 
+impl<A: fmt::Debug, OA, F> fmt::Debug for MapWithState<A, OA, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("MapWithState").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A, OA, F> ParserSealed<'a, I, O, E> for MapWithState<A, OA, F>
 where
     I: Input<'a>,
@@ -464,6 +674,88 @@ where
         }))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::map_with_recovery`].
+pub struct MapWithRecovery<A, OA, F> {
+    pub(crate) parser: A,
+    pub(crate) mapper: F,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<OA>,
+}
+
+impl<A: Copy, OA, F: Copy> Copy for MapWithRecovery<A, OA, F> {}
+impl<A: Clone, OA, F: Clone> Clone for MapWithRecovery<A, OA, F> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            mapper: self.mapper.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<A: fmt::Debug, OA, F> fmt::Debug for MapWithRecovery<A, OA, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("MapWithRecovery")
+            .field(&self.parser)
+            .finish()
+    }
+}
+
+impl<'a, I, O, E, A, OA, F> ParserSealed<'a, I, O, E> for MapWithRecovery<A, OA, F>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    F: Fn(OA, RecoveryInfo<I::Span>) -> O,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.save();
+        let out = self.parser.go::<M>(inp)?;
+        Ok(M::map(out, |out| {
+            let span = inp.span_since(before.offset());
+            let skipped = inp
+                .errors
+                .recovered_since(before.event_count)
+                // SAFETY: Recovered ranges are recorded by the current parse, so the offsets came from the same input
+                .map(|range| unsafe { inp.input.span(range) });
+            (self.mapper)(out, RecoveryInfo { span, skipped })
+        }))
+    }
+
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -486,6 +778,12 @@ impl<A: Clone, OA, F: Clone> Clone for TryMap<A, OA, F> {
     }
 }
 
+impl<A: fmt::Debug, OA, F> fmt::Debug for TryMap<A, OA, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TryMap").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A, OA, F> ParserSealed<'a, I, O, E> for TryMap<A, OA, F>
 where
     I: Input<'a>,
@@ -507,6 +805,13 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx).restricted()
+    }
+
     go_extra!(O);
 }
 
@@ -529,6 +834,14 @@ impl<A: Clone, OA, F: Clone> Clone for TryMapWithState<A, OA, F> {
     }
 }
 
+impl<A: fmt::Debug, OA, F> fmt::Debug for TryMapWithState<A, OA, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TryMapWithState")
+            .field(&self.parser)
+            .finish()
+    }
+}
+
 impl<'a, I, O, E, A, OA, F> ParserSealed<'a, I, O, E> for TryMapWithState<A, OA, F>
 where
     I: Input<'a>,
@@ -550,6 +863,13 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx).restricted()
+    }
+
     go_extra!(O);
 }
 
@@ -572,6 +892,15 @@ impl<A: Clone, OA, O: Clone> Clone for To<A, OA, O> {
     }
 }
 
+impl<A: fmt::Debug, OA, O: fmt::Debug> fmt::Debug for To<A, OA, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("To")
+            .field(&self.parser)
+            .field(&self.to)
+            .finish()
+    }
+}
+
 impl<'a, I, O, E, A, OA> ParserSealed<'a, I, O, E> for To<A, OA, O>
 where
     I: Input<'a>,
@@ -585,6 +914,21 @@ where
         Ok(M::bind(|| self.to.clone()))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -605,6 +949,12 @@ impl<A: Clone, OA> Clone for Ignored<A, OA> {
     }
 }
 
+impl<A: fmt::Debug, OA> fmt::Debug for Ignored<A, OA> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Ignored").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, E, A, OA> ParserSealed<'a, I, (), E> for Ignored<A, OA>
 where
     I: Input<'a>,
@@ -617,6 +967,21 @@ where
         Ok(M::bind(|| ()))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(());
 }
 
@@ -639,6 +1004,12 @@ impl<A: Clone, O> Clone for Unwrapped<A, O> {
     }
 }
 
+impl<A: fmt::Debug, O> fmt::Debug for Unwrapped<A, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Unwrapped").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, E, A, O, U> ParserSealed<'a, I, O, E> for Unwrapped<A, Result<O, U>>
 where
     I: Input<'a>,
@@ -658,6 +1029,21 @@ where
         }))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -679,6 +1065,21 @@ where
         }))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -689,6 +1090,13 @@ pub struct Memoised<A> {
     pub(crate) parser: A,
 }
 
+#[cfg(feature = "memoization")]
+impl<A: fmt::Debug> fmt::Debug for Memoised<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Memoised").field(&self.parser).finish()
+    }
+}
+
 #[cfg(feature = "memoization")]
 impl<'a, I, E, A, O> ParserSealed<'a, I, O, E> for Memoised<A>
 where
@@ -736,6 +1144,91 @@ where
         res
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::named`].
+#[derive(Copy, Clone)]
+pub struct Named<A> {
+    pub(crate) parser: A,
+    pub(crate) name: &'static str,
+}
+
+impl<A> fmt::Debug for Named<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+impl<A> Named<A> {
+    /// Get the name given to this parser.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for Named<A>
+where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, O, E>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        #[cfg(feature = "profile")]
+        if let Some(profiler) = &mut inp.errors.profiler {
+            profiler.enter(self.name);
+            let before = inp.offset().offset;
+            let res = self.parser.go::<M>(inp);
+            let consumed = res.as_ref().ok().map(|_| {
+                let before: usize = before.into();
+                let after: usize = inp.offset().offset.into();
+                after - before
+            });
+            // The profiler can't have been removed by the inner parser
+            if let Some(profiler) = &mut inp.errors.profiler {
+                profiler.exit(consumed);
+            }
+            return res;
+        }
+
+        self.parser.go::<M>(inp)
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some(self.name)
+    }
+
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -758,6 +1251,15 @@ impl<A: Clone, B: Clone, OA, OB, E> Clone for Then<A, B, OA, OB, E> {
     }
 }
 
+impl<A: fmt::Debug, B: fmt::Debug, OA, OB, E> fmt::Debug for Then<A, B, OA, OB, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Then")
+            .field(&self.parser_a)
+            .field(&self.parser_b)
+            .finish()
+    }
+}
+
 impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, (OA, OB), E> for Then<A, B, OA, OB, E>
 where
     I: Input<'a>,
@@ -772,6 +1274,23 @@ where
         Ok(M::combine(a, b, |a: OA, b: OB| (a, b)))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let a = self.parser_a.summarize(cx);
+        cx.then(a, |cx| self.parser_b.summarize(cx))
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser_a.generate(gen)?;
+        self.parser_b.generate(gen)
+    }
+
     go_extra!((OA, OB));
 }
 
@@ -794,6 +1313,15 @@ impl<A: Clone, B: Clone, OA, E> Clone for IgnoreThen<A, B, OA, E> {
     }
 }
 
+impl<A: fmt::Debug, B: fmt::Debug, OA, E> fmt::Debug for IgnoreThen<A, B, OA, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IgnoreThen")
+            .field(&self.parser_a)
+            .field(&self.parser_b)
+            .finish()
+    }
+}
+
 impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, OB, E> for IgnoreThen<A, B, OA, E>
 where
     I: Input<'a>,
@@ -808,6 +1336,23 @@ where
         Ok(M::map(b, |b: OB| b))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let a = self.parser_a.summarize(cx);
+        cx.then(a, |cx| self.parser_b.summarize(cx))
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser_a.generate(gen)?;
+        self.parser_b.generate(gen)
+    }
+
     go_extra!(OB);
 }
 
@@ -830,6 +1375,15 @@ impl<A: Clone, B: Clone, OB, E> Clone for ThenIgnore<A, B, OB, E> {
     }
 }
 
+impl<A: fmt::Debug, B: fmt::Debug, OB, E> fmt::Debug for ThenIgnore<A, B, OB, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ThenIgnore")
+            .field(&self.parser_a)
+            .field(&self.parser_b)
+            .finish()
+    }
+}
+
 impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, OA, E> for ThenIgnore<A, B, OB, E>
 where
     I: Input<'a>,
@@ -844,6 +1398,23 @@ where
         Ok(M::map(a, |a: OA| a))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let a = self.parser_a.summarize(cx);
+        cx.then(a, |cx| self.parser_b.summarize(cx))
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser_a.generate(gen)?;
+        self.parser_b.generate(gen)
+    }
+
     go_extra!(OA);
 }
 
@@ -866,6 +1437,15 @@ impl<A: Clone, B: Clone, O, E> Clone for NestedIn<A, B, O, E> {
     }
 }
 
+impl<A: fmt::Debug, B: fmt::Debug, O, E> fmt::Debug for NestedIn<A, B, O, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NestedIn")
+            .field(&self.parser_a)
+            .field(&self.parser_b)
+            .finish()
+    }
+}
+
 impl<'a, I, E, A, B, O> ParserSealed<'a, I, O, E> for NestedIn<A, B, O, E>
 where
     I: Input<'a>,
@@ -898,6 +1478,125 @@ where
         res
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let b = self.parser_b.summarize(cx);
+        cx.after(&b, |cx| self.parser_a.summarize(cx));
+        // The nested input might not be accepted
+        b.restricted()
+    }
+
+    go_extra!(O);
+}
+
+/// See [`Parser::nested_in_with`].
+pub struct NestedInWith<A, B, F, J, E> {
+    pub(crate) parser_a: A,
+    pub(crate) parser_b: B,
+    pub(crate) map_err: F,
+    #[allow(dead_code)]
+    pub(crate) phantom: EmptyPhantom<(J, E)>,
+}
+
+impl<A: Copy, B: Copy, F: Copy, J, E> Copy for NestedInWith<A, B, F, J, E> {}
+impl<A: Clone, B: Clone, F: Clone, J, E> Clone for NestedInWith<A, B, F, J, E> {
+    fn clone(&self) -> Self {
+        Self {
+            parser_a: self.parser_a.clone(),
+            parser_b: self.parser_b.clone(),
+            map_err: self.map_err.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<A: fmt::Debug, B: fmt::Debug, F, J, E> fmt::Debug for NestedInWith<A, B, F, J, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NestedInWith")
+            .field(&self.parser_a)
+            .field(&self.parser_b)
+            .finish()
+    }
+}
+
+impl<'a, I, E, A, B, F, J, O, EA> ParserSealed<'a, I, O, E>
+    for NestedInWith<A, B, F, Rebased<I::Span, J>, EA>
+where
+    I: Input<'a>,
+    I::Span: Span<Offset = usize> + Clone,
+    E: ParserExtra<'a, I>,
+    J: Input<'a>,
+    J::Span: Span<Offset = usize, Context = ()>,
+    EA: ParserExtra<'a, Rebased<I::Span, J>>,
+    EA::State: Default,
+    EA::Context: Default,
+    A: Parser<'a, Rebased<I::Span, J>, O, EA>,
+    B: Parser<'a, I, (J, I::Span), E>,
+    F: Fn(EA::Error) -> E::Error,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        // Any alternative left behind by `other` describes the outer input, not the reason that the nested parse failed
+        let alt = inp.errors.alt.take();
+        let (inner, base) = match self.parser_b.go::<Emit>(inp) {
+            Ok(out) => out,
+            Err(()) => {
+                inp.restore_alt(alt);
+                return Err(());
+            }
+        };
+        inp.errors.alt = alt;
+        let at = inp.offset().offset;
+
+        // The inner input has its own offsets, so its errors are all located at the end of the outer input it came
+        // from. Their spans have already been translated by `Rebased`. The nested output is only built if it's needed.
+        let mut errs = Vec::new();
+        let mut warns = Vec::new();
+        let output = M::choose(
+            (inner.rebase(base), &mut errs, &mut warns),
+            |(input, errs, warns)| {
+                let res = self.parser_a.parse(input);
+                (*errs, *warns) = (res.errs, res.warns);
+                res.output.ok_or(())
+            },
+            |(input, errs, warns)| {
+                let res = self.parser_a.check(input);
+                (*errs, *warns) = (res.errs, res.warns);
+                res.output.ok_or(())
+            },
+        );
+        for warning in warns {
+            inp.emit_warning(at, (self.map_err)(warning));
+        }
+        match output {
+            Ok(out) => {
+                for err in errs {
+                    inp.emit(at, (self.map_err)(err));
+                }
+                Ok(out)
+            }
+            Err(()) => {
+                // The last error is the one that caused parsing to fail
+                let alt = errs.pop().expect("error but no alt?");
+                for err in errs {
+                    inp.emit(at, (self.map_err)(err));
+                }
+                inp.add_alt_err(at, (self.map_err)(alt));
+                Err(())
+            }
+        }
+    }
+
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        // The nested input might not be accepted
+        self.parser_b.summarize(cx).restricted()
+    }
+
     go_extra!(O);
 }
 
@@ -920,6 +1619,15 @@ impl<A: Clone, B: Clone, OA, I, E> Clone for ThenWithCtx<A, B, OA, I, E> {
     }
 }
 
+impl<A: fmt::Debug, B: fmt::Debug, OA, I, E> fmt::Debug for ThenWithCtx<A, B, OA, I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ThenWithCtx")
+            .field(&self.parser)
+            .field(&self.then)
+            .finish()
+    }
+}
+
 impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, OB, E>
     for ThenWithCtx<A, B, OA, I, extra::Full<E::Error, E::State, OA>>
 where
@@ -935,6 +1643,14 @@ where
         inp.with_ctx(&p1, |inp| self.then.go::<M>(inp))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let a = self.parser.summarize(cx);
+        cx.then(a, |cx| self.then.summarize(cx))
+    }
+
     go_extra!(OB);
 }
 
@@ -971,6 +1687,14 @@ where
 
         inp.with_ctx(ctx, |inp| self.then.next(inp, inner_state))
     }
+
+    fn summarize_iter(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let a = self.parser.summarize(cx);
+        cx.then(a, |cx| self.then.summarize_iter(cx))
+    }
 }
 
 /// See [`Parser::with_ctx`].
@@ -989,6 +1713,12 @@ impl<A: Clone, Ctx: Clone> Clone for WithCtx<A, Ctx> {
     }
 }
 
+impl<A: fmt::Debug, Ctx> fmt::Debug for WithCtx<A, Ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("WithCtx").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A, Ctx> ParserSealed<'a, I, O, E> for WithCtx<A, Ctx>
 where
     I: Input<'a>,
@@ -1001,6 +1731,13 @@ where
         inp.with_ctx(&self.ctx, |inp| self.parser.go::<M>(inp))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
     go_extra!(O);
 }
 
@@ -1009,6 +1746,7 @@ pub struct DelimitedBy<A, B, C, OB, OC> {
     pub(crate) parser: A,
     pub(crate) start: B,
     pub(crate) end: C,
+    pub(crate) recover_unclosed: bool,
     #[allow(dead_code)]
     pub(crate) phantom: EmptyPhantom<(OB, OC)>,
 }
@@ -1020,11 +1758,39 @@ impl<A: Clone, B: Clone, C: Clone, OB, OC> Clone for DelimitedBy<A, B, C, OB, OC
             parser: self.parser.clone(),
             start: self.start.clone(),
             end: self.end.clone(),
+            recover_unclosed: self.recover_unclosed,
             phantom: EmptyPhantom::new(),
         }
     }
 }
 
+impl<A: fmt::Debug, B: fmt::Debug, C: fmt::Debug, OB, OC> fmt::Debug
+    for DelimitedBy<A, B, C, OB, OC>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("DelimitedBy")
+            .field(&self.start)
+            .field(&self.parser)
+            .field(&self.end)
+            .finish()
+    }
+}
+
+impl<A, B, C, OB, OC> DelimitedBy<A, B, C, OB, OC> {
+    /// Recover from a missing closing delimiter by emitting an 'unclosed delimiter' error (see
+    /// [`Error::unclosed_delimiter`]) and producing the output of the inner pattern anyway.
+    ///
+    /// Because the closing delimiter may be missing for other reasons (such as the inner pattern ending early due to
+    /// an error), this works best when the inner pattern also recovers from errors: see
+    /// [`SeparatedBy::recover_until`] for an example.
+    pub fn recover_unclosed(self) -> Self {
+        Self {
+            recover_unclosed: true,
+            ..self
+        }
+    }
+}
+
 impl<'a, I, E, A, B, C, OA, OB, OC> ParserSealed<'a, I, OA, E> for DelimitedBy<A, B, C, OB, OC>
 where
     I: Input<'a>,
@@ -1035,10 +1801,44 @@ where
 {
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, OA> {
+        let before = inp.offset();
         self.start.go::<Check>(inp)?;
+        let after_start = inp.offset();
         let a = self.parser.go::<M>(inp)?;
-        self.end.go::<Check>(inp)?;
-        Ok(a)
+        let before_end = inp.save();
+        match self.end.go::<Check>(inp) {
+            Ok(()) => Ok(a),
+            Err(()) if self.recover_unclosed && inp.errors.begin_recovery(before_end.offset) => {
+                inp.rewind(before_end);
+                let alt = inp.errors.alt.take().expect("error but no alt?");
+                let open_span = inp.span(before..after_start);
+                inp.emit(inp.offset, alt.err.unclosed_delimiter(open_span));
+                inp.mark_recovered(before_end.offset);
+                Ok(a)
+            }
+            Err(()) => Err(()),
+        }
+    }
+
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let start = self.start.summarize(cx);
+        cx.then(start, |cx| {
+            let inner = self.parser.summarize(cx);
+            cx.then(inner, |cx| self.end.summarize(cx))
+        })
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.start.generate(gen)?;
+        self.parser.generate(gen)?;
+        self.end.generate(gen)
     }
 
     go_extra!(OA);
@@ -1063,6 +1863,15 @@ impl<A: Clone, B: Clone, OB> Clone for PaddedBy<A, B, OB> {
     }
 }
 
+impl<A: fmt::Debug, B: fmt::Debug, OB> fmt::Debug for PaddedBy<A, B, OB> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PaddedBy")
+            .field(&self.parser)
+            .field(&self.padding)
+            .finish()
+    }
+}
+
 impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, OA, E> for PaddedBy<A, B, OB>
 where
     I: Input<'a>,
@@ -1078,6 +1887,27 @@ where
         Ok(a)
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let padding = self.padding.summarize(cx);
+        cx.then(padding, |cx| {
+            let inner = self.parser.summarize(cx);
+            cx.then(inner, |cx| self.padding.summarize(cx))
+        })
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.padding.generate(gen)?;
+        self.parser.generate(gen)?;
+        self.padding.generate(gen)
+    }
+
     go_extra!(OA);
 }
 
@@ -1087,6 +1917,25 @@ pub struct Or<A, B> {
     pub(crate) choice: crate::primitive::Choice<(A, B)>,
 }
 
+impl<A: fmt::Debug, B: fmt::Debug> fmt::Debug for Or<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Or")
+            .field(&self.choice.parsers.0)
+            .field(&self.choice.parsers.1)
+            .finish()
+    }
+}
+
+impl<A, B> Or<A, B> {
+    /// Skip whichever of the two alternatives cannot match the next token of the input.
+    ///
+    /// See [`Choice::dispatched`](crate::primitive::Choice::dispatched) for more information. Note that chained calls to [`Parser::or`] nest, so prefer
+    /// [`choice`] when there are many alternatives.
+    pub fn dispatched<Tok>(self) -> crate::primitive::Dispatch<(A, B), Tok> {
+        self.choice.dispatched()
+    }
+}
+
 impl<'a, I, O, E, A, B> ParserSealed<'a, I, O, E> for Or<A, B>
 where
     I: Input<'a>,
@@ -1099,6 +1948,21 @@ where
         self.choice.go::<M>(inp)
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.choice.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.choice.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -1156,6 +2020,12 @@ impl<A: Clone, OA, I, E> Clone for Repeated<A, OA, I, E> {
     }
 }
 
+impl<A: fmt::Debug, OA, I, E> fmt::Debug for Repeated<A, OA, I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Repeated").field(&self.parser).finish()
+    }
+}
+
 impl<'a, A, OA, I, E> Repeated<A, OA, I, E>
 where
     A: Parser<'a, I, OA, E>,
@@ -1271,6 +2141,32 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let item = self.parser.summarize(cx);
+        #[cfg(debug_assertions)]
+        let location = Some(self.location);
+        #[cfg(not(debug_assertions))]
+        let location = None;
+        cx.repeated(
+            item,
+            self.at_least,
+            core::any::type_name::<Self>(),
+            location,
+        )
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        let n = gen.repeats(self.at_least, self.at_most)?;
+        (0..n).try_for_each(|_| self.parser.generate(gen))
+    }
+
     go_extra!(());
 }
 
@@ -1316,6 +2212,21 @@ where
             }
         }
     }
+
+    fn summarize_iter(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        ParserSealed::<I, (), E>::summarize(self, cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate_iter(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        ParserSealed::<I, (), E>::generate(self, gen)
+    }
 }
 
 impl<'a, A, O, I, E> ConfigIterParserSealed<'a, I, O, E> for Repeated<A, O, I, E>
@@ -1359,7 +2270,7 @@ where
 }
 
 /// See [`Parser::separated_by`].
-pub struct SeparatedBy<A, B, OA, OB, I, E> {
+pub struct SeparatedBy<A, B, OA, OB, I, E, C = End<I, E>> {
     pub(crate) parser: A,
     pub(crate) separator: B,
     pub(crate) at_least: usize,
@@ -1367,14 +2278,16 @@ pub struct SeparatedBy<A, B, OA, OB, I, E> {
     pub(crate) at_most: u64,
     pub(crate) allow_leading: bool,
     pub(crate) allow_trailing: bool,
+    pub(crate) recover: bool,
+    pub(crate) closer: C,
     #[cfg(debug_assertions)]
     pub(crate) location: Location<'static>,
     #[allow(dead_code)]
     pub(crate) phantom: EmptyPhantom<(OA, OB, E, I)>,
 }
 
-impl<A: Copy, B: Copy, OA, OB, I, E> Copy for SeparatedBy<A, B, OA, OB, I, E> {}
-impl<A: Clone, B: Clone, OA, OB, I, E> Clone for SeparatedBy<A, B, OA, OB, I, E> {
+impl<A: Copy, B: Copy, OA, OB, I, E, C: Copy> Copy for SeparatedBy<A, B, OA, OB, I, E, C> {}
+impl<A: Clone, B: Clone, OA, OB, I, E, C: Clone> Clone for SeparatedBy<A, B, OA, OB, I, E, C> {
     fn clone(&self) -> Self {
         Self {
             parser: self.parser.clone(),
@@ -1383,6 +2296,8 @@ impl<A: Clone, B: Clone, OA, OB, I, E> Clone for SeparatedBy<A, B, OA, OB, I, E>
             at_most: self.at_most,
             allow_leading: self.allow_leading,
             allow_trailing: self.allow_trailing,
+            recover: self.recover,
+            closer: self.closer.clone(),
             #[cfg(debug_assertions)]
             location: self.location,
             phantom: EmptyPhantom::new(),
@@ -1390,10 +2305,22 @@ impl<A: Clone, B: Clone, OA, OB, I, E> Clone for SeparatedBy<A, B, OA, OB, I, E>
     }
 }
 
-impl<'a, A, B, OA, OB, I, E> SeparatedBy<A, B, OA, OB, I, E>
+impl<A: fmt::Debug, B: fmt::Debug, OA, OB, I, E, C> fmt::Debug
+    for SeparatedBy<A, B, OA, OB, I, E, C>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SeparatedBy")
+            .field(&self.parser)
+            .field(&self.separator)
+            .finish()
+    }
+}
+
+impl<'a, A, B, OA, OB, I, E, C> SeparatedBy<A, B, OA, OB, I, E, C>
 where
     A: Parser<'a, I, OA, E>,
     B: Parser<'a, I, OB, E>,
+    C: Parser<'a, I, (), E>,
     I: Input<'a>,
     E: ParserExtra<'a, I>,
 {
@@ -1496,43 +2423,190 @@ where
     /// ```
     pub fn allow_leading(self) -> Self {
         Self {
-            allow_leading: true,
+            allow_leading: true,
+            ..self
+        }
+    }
+
+    /// Allow a trailing separator to appear after the last item.
+    ///
+    /// Note that if no items are parsed, no leading separator is permitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let numbers = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    ///     .padded()
+    ///     .separated_by(just(','))
+    ///     .allow_trailing()
+    ///     .collect::<Vec<_>>()
+    ///     .delimited_by(just('('), just(')'));
+    ///
+    /// assert_eq!(numbers.parse("(1, 2)").into_result(), Ok(vec!["1", "2"]));
+    /// assert_eq!(numbers.parse("(1, 2,)").into_result(), Ok(vec!["1", "2"]));
+    /// ```
+    pub fn allow_trailing(self) -> Self {
+        Self {
+            allow_trailing: true,
+            ..self
+        }
+    }
+
+    /// Recover from errors within the list, assuming that it continues until the end of the input. See
+    /// [`SeparatedBy::recover_until`].
+    pub fn recover(self) -> Self {
+        Self {
+            recover: true,
             ..self
         }
     }
 
-    /// Allow a trailing separator to appear after the last item.
+    /// Recover from errors within the list, which continues until the given closing pattern (usually the closing
+    /// delimiter of the list, such as `]`).
     ///
-    /// Note that if no items are parsed, no leading separator is permitted.
+    /// When recovering, the list tolerates:
+    ///
+    /// - Malformed items, which are skipped up to the next separator or the closing pattern
+    /// - Doubled separators, such as `1,,2`
+    /// - Missing separators, such as `1 2`, so long as another item follows
+    ///
+    /// In each case, an error is emitted and parsing of the list continues, producing a partial collection. The closing
+    /// pattern is only looked for, not consumed. Combine with [`DelimitedBy::recover_unclosed`] to also recover from a
+    /// missing closing delimiter.
     ///
     /// # Examples
     ///
     /// ```
     /// # use chumsky::prelude::*;
-    /// let numbers = text::int::<_, _, extra::Err<Simple<char>>>(10)
+    /// let list = text::int::<_, _, extra::Err<Rich<char>>>(10)
     ///     .padded()
     ///     .separated_by(just(','))
-    ///     .allow_trailing()
+    ///     .recover_until(just(']').ignored())
     ///     .collect::<Vec<_>>()
-    ///     .delimited_by(just('('), just(')'));
+    ///     .delimited_by(just('['), just(']'))
+    ///     .recover_unclosed();
     ///
-    /// assert_eq!(numbers.parse("(1, 2)").into_result(), Ok(vec!["1", "2"]));
-    /// assert_eq!(numbers.parse("(1, 2,)").into_result(), Ok(vec!["1", "2"]));
+    /// let (out, errs) = list.parse("[1, x+y, 2,, 3 4").into_output_errors();
+    /// assert_eq!(out, Some(vec!["1", "2", "3", "4"]));
+    /// assert_eq!(
+    ///     errs.iter().map(|e| e.span().into_range()).collect::<Vec<_>>(),
+    ///     vec![
+    ///         4..5, // Malformed item
+    ///         11..12, // Doubled separator
+    ///         15..16, // Missing separator
+    ///         0..1, // Unclosed delimiter
+    ///     ],
+    /// );
+    /// assert_eq!(errs[3].to_string(), "unclosed delimiter");
     /// ```
-    pub fn allow_trailing(self) -> Self {
-        Self {
-            allow_trailing: true,
-            ..self
+    pub fn recover_until<C2>(self, closer: C2) -> SeparatedBy<A, B, OA, OB, I, E, C2>
+    where
+        C2: Parser<'a, I, (), E>,
+    {
+        SeparatedBy {
+            parser: self.parser,
+            separator: self.separator,
+            at_least: self.at_least,
+            at_most: self.at_most,
+            allow_leading: self.allow_leading,
+            allow_trailing: self.allow_trailing,
+            recover: true,
+            closer,
+            #[cfg(debug_assertions)]
+            location: self.location,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
+    // Check whether the list has ended, without consuming input or affecting errors
+    fn at_end_of_list(&self, inp: &mut InputRef<'a, '_, I, E>) -> bool {
+        let before = inp.save();
+        let alt = inp.errors.alt.take();
+        let at_end = inp.next_maybe_inner().1.is_none() || {
+            inp.rewind(before);
+            self.closer.go::<Check>(inp).is_ok()
+        };
+        inp.errors.alt = alt;
+        inp.rewind(before);
+        at_end
+    }
+
+    // Check whether a separator appears next, without consuming input or affecting errors. A separator that matches
+    // no input doesn't count, since skipping up to it would never make progress.
+    fn at_separator(&self, inp: &mut InputRef<'a, '_, I, E>) -> bool {
+        let before = inp.save();
+        let alt = inp.errors.alt.take();
+        let at_separator =
+            self.separator.go::<Check>(inp).is_ok() && inp.offset.into() != before.offset.into();
+        inp.errors.alt = alt;
+        inp.rewind(before);
+        at_separator
+    }
+
+    // The fallible part of `IterParserSealed::next` when the list has been told to recover from errors. Kept separate
+    // so as not to slow down the common case.
+    #[inline(never)]
+    fn next_recovering<'parse, M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        state: &mut usize,
+        mut before_separator: Marker<'a, 'parse, I>,
+    ) -> IPResult<M, OA> {
+        loop {
+            let before_item = inp.save();
+            match self.parser.go::<M>(inp) {
+                Ok(item) => {
+                    *state += 1;
+                    break Ok(Some(item));
+                }
+                Err(()) => {
+                    inp.rewind(before_item);
+                    if self.at_end_of_list(inp) || !inp.errors.begin_recovery(inp.offset) {
+                        // Either the list has ended normally, or we may not recover: fall back to the usual rules
+                        break if *state < self.at_least {
+                            inp.rewind(before_separator);
+                            Err(())
+                        } else {
+                            if !self.allow_trailing {
+                                inp.rewind(before_separator);
+                            }
+                            Ok(None)
+                        };
+                    }
+
+                    // Skip the malformed item (if a separator follows immediately, the separator was doubled)
+                    let alt = inp.errors.alt.take().expect("error but no alt?");
+                    while !self.at_separator(inp) && !self.at_end_of_list(inp) {
+                        inp.next_maybe_inner();
+                    }
+                    inp.emit(inp.offset, alt.err);
+                    inp.mark_recovered(before_item.offset);
+
+                    let before_next_separator = inp.save();
+                    if self.separator.go::<Check>(inp).is_err()
+                        || inp.offset.into() == before_item.offset.into()
+                    {
+                        // We've reached the end of the list, or can make no further progress
+                        inp.errors.alt.take();
+                        inp.rewind(before_next_separator);
+                        break Ok(None);
+                    }
+                    before_separator = before_next_separator;
+                }
+            }
         }
     }
 }
 
-impl<'a, I, E, A, B, OA, OB> IterParserSealed<'a, I, OA, E> for SeparatedBy<A, B, OA, OB, I, E>
+impl<'a, I, E, A, B, OA, OB, C> IterParserSealed<'a, I, OA, E>
+    for SeparatedBy<A, B, OA, OB, I, E, C>
 where
     I: Input<'a>,
     E: ParserExtra<'a, I>,
     A: Parser<'a, I, OA, E>,
     B: Parser<'a, I, OB, E>,
+    C: Parser<'a, I, (), E>,
 {
     type IterState<M: Mode> = usize
     where
@@ -1566,6 +2640,42 @@ where
                 Ok(()) => {
                     // Do nothing
                 }
+                Err(()) if self.recover => {
+                    inp.rewind(before_separator);
+                    if self.at_end_of_list(inp) || !inp.errors.begin_recovery(inp.offset) {
+                        return if *state < self.at_least {
+                            Err(())
+                        } else {
+                            Ok(None)
+                        };
+                    }
+                    let sep_alt = inp.errors.alt.take().expect("error but no alt?");
+
+                    // Tolerate a missing separator if another item follows
+                    let before_item = inp.save();
+                    inp.mark_recovered(before_item.offset);
+                    if let Ok(item) = self.parser.go::<M>(inp) {
+                        inp.emit(sep_alt.pos, sep_alt.err);
+                        *state += 1;
+                        return Ok(Some(item));
+                    }
+                    inp.errors.alt.take();
+                    inp.rewind(before_item);
+
+                    // Otherwise, skip whatever is in the way of the next separator
+                    while !self.at_separator(inp) && !self.at_end_of_list(inp) {
+                        inp.next_maybe_inner();
+                    }
+                    inp.emit(inp.offset, sep_alt.err);
+                    inp.mark_recovered(before_item.offset);
+                    let before_next_separator = inp.save();
+                    if self.separator.go::<Check>(inp).is_err() {
+                        // We've reached the end of the list
+                        inp.errors.alt.take();
+                        inp.rewind(before_next_separator);
+                        return Ok(None);
+                    }
+                }
                 Err(()) if *state < self.at_least => {
                     inp.rewind(before_separator);
                     return Err(());
@@ -1577,6 +2687,10 @@ where
             }
         }
 
+        if self.recover {
+            return self.next_recovering::<M>(inp, state, before_separator);
+        }
+
         let before_item = inp.save();
         match self.parser.go::<M>(inp) {
             Ok(item) => {
@@ -1605,14 +2719,30 @@ where
             }
         }
     }
+
+    fn summarize_iter(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        ParserSealed::<I, (), E>::summarize(self, cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate_iter(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        ParserSealed::<I, (), E>::generate(self, gen)
+    }
 }
 
-impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, (), E> for SeparatedBy<A, B, OA, OB, I, E>
+impl<'a, I, E, A, B, OA, OB, C> ParserSealed<'a, I, (), E> for SeparatedBy<A, B, OA, OB, I, E, C>
 where
     I: Input<'a>,
     E: ParserExtra<'a, I>,
     A: Parser<'a, I, OA, E>,
     B: Parser<'a, I, OB, E>,
+    C: Parser<'a, I, (), E>,
 {
     #[inline(always)]
     fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, ()> {
@@ -1637,6 +2767,57 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let item = self.parser.summarize(cx);
+        let separator = cx.after(&item, |cx| self.separator.summarize(cx));
+        // Only a problem if neither the item nor the separator consume input
+        if item.nullable == Some(true) && separator.nullable == Some(true) {
+            #[cfg(debug_assertions)]
+            let location = Some(self.location);
+            #[cfg(not(debug_assertions))]
+            let location = None;
+            cx.report(Problem::NullableRepetition {
+                parser: core::any::type_name::<Self>(),
+                location,
+            });
+        }
+        let mut summary = item.restricted();
+        if self.allow_leading {
+            summary = separator.or_empty().then(summary).restricted();
+        }
+        if self.at_least == 0 {
+            summary = summary.or_empty();
+        }
+        if self.recover {
+            summary = summary.recovering();
+        }
+        summary
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        let n = gen.repeats(self.at_least, self.at_most)?;
+        if n > 0 && self.allow_leading && gen.flip()? {
+            self.separator.generate(gen)?;
+        }
+        for i in 0..n {
+            if i > 0 {
+                self.separator.generate(gen)?;
+            }
+            self.parser.generate(gen)?;
+        }
+        if n > 0 && self.allow_trailing && gen.flip()? {
+            self.separator.generate(gen)?;
+        }
+        Ok(())
+    }
+
     go_extra!(());
 }
 
@@ -1657,6 +2838,12 @@ impl<A: Clone, O> Clone for Enumerate<A, O> {
     }
 }
 
+impl<A: fmt::Debug, O> fmt::Debug for Enumerate<A, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Enumerate").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A> IterParserSealed<'a, I, (usize, O), E> for Enumerate<A, O>
 where
     A: IterParser<'a, I, O, E>,
@@ -1688,6 +2875,21 @@ where
         state.0 += 1;
         Ok(out)
     }
+
+    fn summarize_iter(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize_iter(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate_iter(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate_iter(gen)
+    }
 }
 
 /// See [`IterParser::collect`].
@@ -1711,6 +2913,12 @@ impl<A: Clone, O, C> Clone for Collect<A, O, C> {
     }
 }
 
+impl<A: fmt::Debug, O, C> fmt::Debug for Collect<A, O, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Collect").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A, C> ParserSealed<'a, I, C, E> for Collect<A, O, C>
 where
     I: Input<'a>,
@@ -1741,6 +2949,21 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize_iter(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate_iter(gen)
+    }
+
     go_extra!(C);
 }
 
@@ -1761,6 +2984,12 @@ impl<A: Clone, O, C> Clone for CollectExactly<A, O, C> {
     }
 }
 
+impl<A: fmt::Debug, O, C> fmt::Debug for CollectExactly<A, O, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CollectExactly").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A, C> ParserSealed<'a, I, C, E> for CollectExactly<A, O, C>
 where
     I: Input<'a>,
@@ -1799,6 +3028,13 @@ where
         Ok(M::map(output, |output| unsafe { C::take(output) }))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize_iter(cx).restricted()
+    }
+
     go_extra!(C);
 }
 
@@ -1808,6 +3044,12 @@ pub struct OrNot<A> {
     pub(crate) parser: A,
 }
 
+impl<A: fmt::Debug> fmt::Debug for OrNot<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("OrNot").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A> ParserSealed<'a, I, Option<O>, E> for OrNot<A>
 where
     I: Input<'a>,
@@ -1826,6 +3068,24 @@ where
         })
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx).or_empty()
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        gen.alternatives(2, |gen, i| match i {
+            0 => self.parser.generate(gen),
+            _ => Ok(()),
+        })
+    }
+
     go_extra!(Option<O>);
 }
 
@@ -1846,6 +3106,12 @@ impl<A: Clone, OA> Clone for Not<A, OA> {
     }
 }
 
+impl<A: fmt::Debug, OA> fmt::Debug for Not<A, OA> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Not").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, E, A, OA> ParserSealed<'a, I, (), E> for Not<A, OA>
 where
     I: ValueInput<'a>,
@@ -1874,6 +3140,15 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        // The inner pattern is only used for lookahead, so succeeds wherever it fails
+        self.parser.summarize(cx);
+        Summary::lookahead(None)
+    }
+
     go_extra!(());
 }
 
@@ -1896,6 +3171,15 @@ impl<A: Clone, B: Clone, OB> Clone for AndIs<A, B, OB> {
     }
 }
 
+impl<A: fmt::Debug, B: fmt::Debug, OB> fmt::Debug for AndIs<A, B, OB> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("AndIs")
+            .field(&self.parser_a)
+            .field(&self.parser_b)
+            .finish()
+    }
+}
+
 impl<'a, I, E, A, B, OA, OB> ParserSealed<'a, I, OA, E> for AndIs<A, B, OB>
 where
     I: Input<'a>,
@@ -1933,6 +3217,20 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let a = self.parser_a.summarize(cx).restricted();
+        let b = self.parser_b.summarize(cx);
+        // Both patterns must match at the same position, so the next token must be acceptable to both
+        if b.nullable == Some(false) {
+            a.and(b)
+        } else {
+            a
+        }
+    }
+
     go_extra!(OA);
 }
 
@@ -1961,6 +3259,15 @@ impl<F: Clone, A: Clone, B: Clone, OA, E> Clone for Foldr<F, A, B, OA, E> {
     }
 }
 
+impl<F, A: fmt::Debug, B: fmt::Debug, OA, E> fmt::Debug for Foldr<F, A, B, OA, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Foldr")
+            .field(&self.parser_a)
+            .field(&self.parser_b)
+            .finish()
+    }
+}
+
 impl<'a, I, F, A, B, O, OA, E> ParserSealed<'a, I, O, E> for Foldr<F, A, B, OA, E>
 where
     I: Input<'a>,
@@ -2001,6 +3308,23 @@ where
         }))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let a = self.parser_a.summarize_iter(cx);
+        cx.then(a, |cx| self.parser_b.summarize(cx))
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser_a.generate_iter(gen)?;
+        self.parser_b.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -2029,6 +3353,15 @@ impl<F: Clone, A: Clone, B: Clone, OA, E> Clone for FoldrWithState<F, A, B, OA,
     }
 }
 
+impl<F, A: fmt::Debug, B: fmt::Debug, OA, E> fmt::Debug for FoldrWithState<F, A, B, OA, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("FoldrWithState")
+            .field(&self.parser_a)
+            .field(&self.parser_b)
+            .finish()
+    }
+}
+
 impl<'a, I, F, A, B, O, OA, E> ParserSealed<'a, I, O, E> for FoldrWithState<F, A, B, OA, E>
 where
     I: Input<'a>,
@@ -2072,6 +3405,23 @@ where
         }))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let a = self.parser_a.summarize_iter(cx);
+        cx.then(a, |cx| self.parser_b.summarize(cx))
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser_a.generate_iter(gen)?;
+        self.parser_b.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -2100,6 +3450,15 @@ impl<F: Clone, A: Clone, B: Clone, OB, E> Clone for Foldl<F, A, B, OB, E> {
     }
 }
 
+impl<F, A: fmt::Debug, B: fmt::Debug, OB, E> fmt::Debug for Foldl<F, A, B, OB, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Foldl")
+            .field(&self.parser_a)
+            .field(&self.parser_b)
+            .finish()
+    }
+}
+
 impl<'a, I, F, A, B, O, OB, E> ParserSealed<'a, I, O, E> for Foldl<F, A, B, OB, E>
 where
     I: Input<'a>,
@@ -2134,6 +3493,23 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let a = self.parser_a.summarize(cx);
+        cx.then(a, |cx| self.parser_b.summarize_iter(cx))
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser_a.generate(gen)?;
+        self.parser_b.generate_iter(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -2162,6 +3538,15 @@ impl<F: Clone, A: Clone, B: Clone, OB, E> Clone for FoldlWithState<F, A, B, OB,
     }
 }
 
+impl<F, A: fmt::Debug, B: fmt::Debug, OB, E> fmt::Debug for FoldlWithState<F, A, B, OB, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("FoldlWithState")
+            .field(&self.parser_a)
+            .field(&self.parser_b)
+            .finish()
+    }
+}
+
 impl<'a, I, F, A, B, O, OB, E> ParserSealed<'a, I, O, E> for FoldlWithState<F, A, B, OB, E>
 where
     I: Input<'a>,
@@ -2197,6 +3582,23 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let a = self.parser_a.summarize(cx);
+        cx.then(a, |cx| self.parser_b.summarize_iter(cx))
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser_a.generate(gen)?;
+        self.parser_b.generate_iter(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -2207,6 +3609,12 @@ pub struct Rewind<A> {
     pub(crate) parser: A,
 }
 
+impl<A: fmt::Debug> fmt::Debug for Rewind<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Rewind").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for Rewind<A>
 where
     I: Input<'a>,
@@ -2225,6 +3633,13 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::lookahead(self.parser.summarize(cx).first)
+    }
+
     go_extra!(O);
 }
 
@@ -2235,6 +3650,12 @@ pub struct MapErr<A, F> {
     pub(crate) mapper: F,
 }
 
+impl<A: fmt::Debug, F> fmt::Debug for MapErr<A, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("MapErr").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A, F> ParserSealed<'a, I, O, E> for MapErr<A, F>
 where
     I: Input<'a>,
@@ -2258,6 +3679,21 @@ where
         res
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx).relabelled()
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -2303,6 +3739,14 @@ pub struct MapErrWithState<A, F> {
     pub(crate) mapper: F,
 }
 
+impl<A: fmt::Debug, F> fmt::Debug for MapErrWithState<A, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("MapErrWithState")
+            .field(&self.parser)
+            .finish()
+    }
+}
+
 impl<'a, I, O, E, A, F> ParserSealed<'a, I, O, E> for MapErrWithState<A, F>
 where
     I: Input<'a>,
@@ -2328,6 +3772,21 @@ where
         res
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx).relabelled()
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -2350,6 +3809,12 @@ impl<A: Clone, OA, F: Clone> Clone for Validate<A, OA, F> {
     }
 }
 
+impl<A: fmt::Debug, OA, F> fmt::Debug for Validate<A, OA, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Validate").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, OA, U, E, A, F> ParserSealed<'a, I, U, E> for Validate<A, OA, F>
 where
     I: Input<'a>,
@@ -2368,12 +3833,31 @@ where
         let span = inp.span_since(before);
         let mut emitter = Emitter::new();
         let out = (self.validator)(out, span, &mut emitter);
-        for err in emitter.errors() {
+        let (errors, warnings) = emitter.into_parts();
+        for err in errors {
             inp.emit(inp.offset, err);
         }
+        for warning in warnings {
+            inp.emit_warning(inp.offset, warning);
+        }
         Ok(M::bind(|| out))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(U);
 }
 
@@ -2498,4 +3982,95 @@ mod tests {
             Ok((vec!['-', '-', '-'], ',')),
         )
     }
+
+    #[test]
+    fn separated_by_recover() {
+        let parser = one_of::<_, _, extra::Err<Rich<char>>>('0'..='9')
+            .separated_by(just(','))
+            .recover()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            parser.parse("1,2,3").into_output_errors(),
+            (Some(vec!['1', '2', '3']), vec![])
+        );
+
+        let (out, errs) = parser.parse("1,,2,ab,3").into_output_errors();
+        assert_eq!(out, Some(vec!['1', '2', '3']));
+        assert_eq!(
+            errs.iter()
+                .map(|e| e.span().into_range())
+                .collect::<Vec<_>>(),
+            vec![2..3, 5..6],
+        );
+
+        // Skipping stops at the end of input
+        let (out, errs) = parser.parse("1,2x").into_output_errors();
+        assert_eq!(out, Some(vec!['1', '2']));
+        assert_eq!(errs.len(), 1);
+
+        // A separator that matches no input doesn't stop skipping
+        let parser = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .separated_by(just(',').or_not())
+            .recover()
+            .collect::<Vec<_>>();
+        let (out, errs) = parser.parse("1,2,x").into_output_errors();
+        assert_eq!(out, Some(vec!["1", "2"]));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(4, 5));
+    }
+
+    #[test]
+    fn separated_by_recover_missing_separator() {
+        let parser = one_of::<_, _, extra::Err<Rich<char>>>('0'..='9')
+            .separated_by(just(','))
+            .recover()
+            .collect::<Vec<_>>()
+            .map_with_recovery(|items, info| (items, info.recovered()));
+
+        let (out, errs) = parser.parse("1,23").into_output_errors();
+        assert_eq!(out, Some((vec!['1', '2', '3'], true)));
+        assert_eq!(errs.len(), 1);
+
+        // The recovery counts towards the limit on recoveries
+        let res = parser.parse_with_options("1,23", crate::ParseOptions::new().max_recoveries(0));
+        assert!(!res.has_output());
+    }
+
+    #[test]
+    fn separated_by_recover_nested() {
+        let list = recursive(|list| {
+            one_of::<_, _, extra::Err<Rich<char>>>('0'..='9')
+                .to(0)
+                .or(list)
+                .separated_by(just(','))
+                .recover_until(just(']').ignored())
+                .collect::<Vec<_>>()
+                .delimited_by(just('['), just(']'))
+                .map(|items| items.len())
+        });
+
+        // Skipping the malformed item stops at the inner closing delimiter
+        let (out, errs) = list.parse("[[1,x],[2,3],4]").into_output_errors();
+        assert_eq!(out, Some(3));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(4, 5));
+    }
+
+    #[test]
+    fn delimited_by_recover_unclosed() {
+        let parser = one_of::<_, _, extra::Err<Rich<char>>>('0'..='9')
+            .repeated()
+            .collect::<String>()
+            .delimited_by(just('('), just(')'))
+            .recover_unclosed();
+
+        assert_eq!(parser.parse("(12)").into_result(), Ok("12".to_string()));
+
+        let (out, errs) = parser.parse("(12").into_output_errors();
+        assert_eq!(out, Some("12".to_string()));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(0, 1));
+        assert_eq!(errs[0].to_string(), "unclosed delimiter");
+    }
 }
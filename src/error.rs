@@ -106,6 +106,80 @@ pub trait Error<'a, I: Input<'a>>: Sized {
     ) -> Self {
         Self::expected_found(expected, found, span)
     }
+
+    /// Create an error indicating that parsing was halted because one of the limits given to
+    /// [`Parser::parse_with_options`] was reached.
+    ///
+    /// By default, this creates an error that expects nothing and finds the end of input.
+    #[inline(always)]
+    fn too_many_errors(span: I::Span) -> Self {
+        Self::expected_found(None, None, span)
+    }
+
+    /// Get the tokens that this error expected to find, in the order that they were expected.
+    ///
+    /// This is used by [`recovery::repair`] to find candidate tokens to insert. By default, no tokens are returned
+    /// (i.e: the error does not track what was expected).
+    fn expected_tokens(&self) -> Vec<&MaybeRef<'a, I::Token>> {
+        Vec::new()
+    }
+
+    /// Create an error indicating that a delimiter was never closed, given the error produced when the closing
+    /// delimiter was expected and the span of the opening delimiter. See [`DelimitedBy::recover_unclosed`].
+    ///
+    /// By default, the error is returned unchanged.
+    #[inline(always)]
+    fn unclosed_delimiter(self, open_span: I::Span) -> Self {
+        #![allow(unused_variables)]
+        self
+    }
+
+    /// Describe a repair made to the input by [`recovery::repair`] in order to recover from this error.
+    ///
+    /// By default, the error is returned unchanged.
+    #[inline(always)]
+    fn repaired(self, edit: recovery::Edit<'a, I::Token>, span: I::Span) -> Self {
+        #![allow(unused_variables)]
+        self
+    }
+
+    /// Create an error indicating that the input did not match the given regex pattern. See [`regex::regex`].
+    ///
+    /// By default, this creates an error that expects nothing.
+    #[cfg(feature = "regex")]
+    #[inline(always)]
+    fn expected_regex(pattern: &str, found: Option<MaybeRef<'a, I::Token>>, span: I::Span) -> Self {
+        #![allow(unused_variables)]
+        Self::expected_found(None, found, span)
+    }
+
+    /// Create an error indicating that a token of one of the given kinds was expected. See [`kind::one_of_kinds`].
+    ///
+    /// By default, this creates an error that expects nothing.
+    #[inline(always)]
+    fn expected_kinds(
+        expected: KindSet<I::Token>,
+        found: Option<MaybeRef<'a, I::Token>>,
+        span: I::Span,
+    ) -> Self
+    where
+        I::Token: TokenKind,
+    {
+        #![allow(unused_variables)]
+        Self::expected_found(None, found, span)
+    }
+}
+
+/// The severity of a diagnostic produced while parsing. See [`ParseResult::diagnostics`].
+///
+/// Diagnostics emitted with [`Emitter::emit`] are errors, while those emitted with [`Emitter::warn`] (or
+/// [`InputRef::warn`]) are warnings. Only errors cause parsing to be considered a failure.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// A non-fatal diagnostic, such as a lint or a deprecation notice.
+    Warning,
+    /// An error.
+    Error,
 }
 
 /// A ZST error type that tracks only whether a parse error occurred at all. This type is for when
@@ -232,6 +306,10 @@ pub enum RichPattern<'a, T, L = &'static str> {
     Token(MaybeRef<'a, T>),
     /// A labelled pattern was expected.
     Label(L),
+    /// A match for the given regex pattern was expected (see `regex::regex`).
+    Regex(String),
+    /// A token of the given kind was expected, described by its name (see [`kind::TokenKind::kind_name`]).
+    Kind(&'static str),
     /// The end of input was expected.
     EndOfInput,
 }
@@ -248,6 +326,8 @@ impl<'a, T, L> RichPattern<'a, T, L> {
         match self {
             Self::Token(t) => RichPattern::Token(f(t.into_inner()).into()),
             Self::Label(s) => RichPattern::Label(s),
+            Self::Regex(pattern) => RichPattern::Regex(pattern),
+            Self::Kind(name) => RichPattern::Kind(name),
             Self::EndOfInput => RichPattern::EndOfInput,
         }
     }
@@ -260,6 +340,8 @@ impl<'a, T, L> RichPattern<'a, T, L> {
         match self {
             Self::Token(tok) => RichPattern::Token(tok.into_owned()),
             Self::Label(label) => RichPattern::Label(label),
+            Self::Regex(pattern) => RichPattern::Regex(pattern),
+            Self::Kind(name) => RichPattern::Kind(name),
             Self::EndOfInput => RichPattern::EndOfInput,
         }
     }
@@ -277,6 +359,8 @@ impl<'a, T, L> RichPattern<'a, T, L> {
                 write!(f, "'")
             }
             Self::Label(label) => fmt_label(label, f),
+            Self::Regex(pattern) => write!(f, "/{}/", pattern),
+            Self::Kind(name) => write!(f, "{}", name),
             Self::EndOfInput => write!(f, "end of input"),
         }
     }
@@ -291,6 +375,8 @@ where
         match self {
             Self::Token(t) => write!(f, "{:?}", t),
             Self::Label(label) => write!(f, "{:?}", label),
+            Self::Regex(pattern) => write!(f, "/{}/", pattern),
+            Self::Kind(name) => write!(f, "{}", name),
             Self::EndOfInput => write!(f, "end of input"),
         }
     }
@@ -305,6 +391,8 @@ where
         match self {
             Self::Token(t) => write!(f, "'{}'", &**t),
             Self::Label(s) => write!(f, "{}", s),
+            Self::Regex(pattern) => write!(f, "/{}/", pattern),
+            Self::Kind(name) => write!(f, "{}", name),
             Self::EndOfInput => write!(f, "end of input"),
         }
     }
@@ -323,6 +411,8 @@ pub enum RichReason<'a, T, L = &'static str> {
     },
     /// An error with a custom message
     Custom(String),
+    /// The input was repaired by error recovery (see [`recovery::repair`])
+    Repaired(recovery::Edit<'a, T>),
     /// Multiple unrelated reasons were merged
     // TODO: Should we really do this? Possibly better to just unify the unrelated reasons. It's not like consumers
     // probably care about reporting 5 different errors for the same location anyway!
@@ -335,6 +425,8 @@ impl<'a, T, L> RichReason<'a, T, L> {
         match self {
             Self::ExpectedFound { found, .. } => found.as_deref(),
             Self::Custom(_) => None,
+            Self::Repaired(recovery::Edit::Delete(found)) => Some(found),
+            Self::Repaired(recovery::Edit::Insert(_)) => None,
             Self::Many(many) => many.iter().find_map(|r| r.found()),
         }
     }
@@ -350,6 +442,7 @@ impl<'a, T, L> RichReason<'a, T, L> {
                 found: found.map(MaybeRef::into_owned),
             },
             Self::Custom(msg) => RichReason::Custom(msg),
+            Self::Repaired(edit) => RichReason::Repaired(edit.into_owned()),
             Self::Many(many) => {
                 RichReason::Many(many.into_iter().map(RichReason::into_owned).collect())
             }
@@ -360,7 +453,7 @@ impl<'a, T, L> RichReason<'a, T, L> {
     fn take_found(&mut self) -> Option<MaybeRef<'a, T>> {
         match self {
             RichReason::ExpectedFound { found, .. } => found.take(),
-            RichReason::Custom(_) => None,
+            RichReason::Custom(_) | RichReason::Repaired(_) => None,
             RichReason::Many(many) => many.iter_mut().find_map(|r| r.take_found()),
         }
     }
@@ -386,6 +479,7 @@ impl<'a, T, L> RichReason<'a, T, L> {
                     found: found.map(|found| f(found.into_inner()).into()),
                 },
                 RichReason::Custom(msg) => RichReason::Custom(msg),
+                RichReason::Repaired(edit) => RichReason::Repaired(edit.map_token(&mut f)),
                 RichReason::Many(reasons) => {
                     RichReason::Many(reasons.into_iter().map(|r| map_token_inner(r, f)).collect())
                 }
@@ -435,6 +529,18 @@ impl<'a, T, L> RichReason<'a, T, L> {
                     fmt_span(span, f)?;
                 }
             }
+            RichReason::Repaired(edit) => {
+                let (verb, tok) = match edit {
+                    recovery::Edit::Insert(tok) => ("inserted missing", tok),
+                    recovery::Edit::Delete(tok) => ("deleted unexpected", tok),
+                };
+                write!(f, "{} ", verb)?;
+                write_token(f, &mut fmt_token, Some(tok))?;
+                if let Some(span) = span {
+                    write!(f, " at ")?;
+                    fmt_span(span, f)?;
+                }
+            }
             RichReason::Many(_) => {
                 write!(f, "multiple errors")?;
                 if let Some(span) = span {
@@ -513,11 +619,29 @@ where
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Rich<'a, T, S = SimpleSpan<usize>, L = &'static str> {
     span: S,
-    reason: Box<RichReason<'a, T, L>>,
+    inner: Box<RichInner<'a, T, L>>,
     #[cfg(feature = "label")]
     context: Vec<(L, S)>,
 }
 
+// The parts of a `Rich` error that are kept behind a single allocation, since errors are moved around a lot and so
+// should be kept small
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct RichInner<'a, T, L> {
+    reason: RichReason<'a, T, L>,
+    suggestions: Vec<String>,
+}
+
+impl<'a, T, L> RichInner<'a, T, L> {
+    #[inline]
+    fn boxed(reason: RichReason<'a, T, L>) -> Box<Self> {
+        Box::new(Self {
+            reason,
+            suggestions: Vec::new(),
+        })
+    }
+}
+
 impl<'a, T, S, L> Rich<'a, T, S, L> {
     fn inner_fmt(
         &self,
@@ -527,13 +651,24 @@ impl<'a, T, S, L> Rich<'a, T, S, L> {
         fmt_label: impl FnMut(&L, &mut fmt::Formatter<'_>) -> fmt::Result,
         with_spans: bool,
     ) -> fmt::Result {
-        self.reason.inner_fmt(
+        self.inner.reason.inner_fmt(
             f,
             fmt_token,
             fmt_span,
             fmt_label,
             if with_spans { Some(&self.span) } else { None },
-        )
+        )?;
+        if let Some((last, init)) = self.inner.suggestions.split_last() {
+            write!(f, ", did you mean ")?;
+            for suggestion in init {
+                write!(f, "'{}', ", suggestion)?;
+            }
+            if !init.is_empty() {
+                write!(f, "or ")?;
+            }
+            write!(f, "'{}'?", last)?;
+        }
+        Ok(())
     }
 }
 
@@ -543,7 +678,7 @@ impl<'a, T, S, L> Rich<'a, T, S, L> {
     pub fn custom<M: ToString>(span: S, msg: M) -> Self {
         Rich {
             span,
-            reason: Box::new(RichReason::Custom(msg.to_string())),
+            inner: RichInner::boxed(RichReason::Custom(msg.to_string())),
             #[cfg(feature = "label")]
             context: Vec::new(),
         }
@@ -556,17 +691,17 @@ impl<'a, T, S, L> Rich<'a, T, S, L> {
 
     /// Get the reason for this error.
     pub fn reason(&self) -> &RichReason<'a, T, L> {
-        &self.reason
+        &self.inner.reason
     }
 
     /// Take the reason from this error.
     pub fn into_reason(self) -> RichReason<'a, T, L> {
-        *self.reason
+        self.inner.reason
     }
 
     /// Get the token found by this error when parsing. `None` implies that the error expected the end of input.
     pub fn found(&self) -> Option<&T> {
-        self.reason.found()
+        self.inner.reason.found()
     }
 
     /// Return an iterator over the labelled contexts of this error, from least general to most.
@@ -583,9 +718,18 @@ impl<'a, T, S, L> Rich<'a, T, S, L> {
     where
         T: Clone,
     {
+        let RichInner {
+            reason,
+            suggestions,
+        } = *self.inner;
         Rich {
-            reason: Box::new(self.reason.into_owned()),
-            ..self
+            span: self.span,
+            inner: Box::new(RichInner {
+                reason: reason.into_owned(),
+                suggestions,
+            }),
+            #[cfg(feature = "label")]
+            context: self.context,
         }
     }
 
@@ -597,15 +741,101 @@ impl<'a, T, S, L> Rich<'a, T, S, L> {
         ) {
             match reason {
                 RichReason::ExpectedFound { expected, .. } => v.extend(expected.iter()),
-                RichReason::Custom(_) => {}
+                RichReason::Custom(_) | RichReason::Repaired(_) => {}
                 RichReason::Many(many) => many.iter().for_each(|r| push_expected(r, v)),
             }
         }
         let mut v = Vec::new();
-        push_expected(&self.reason, &mut v);
+        push_expected(&self.inner.reason, &mut v);
         v.into_iter()
     }
 
+    /// Get an iterator over the 'did you mean' suggestions associated with this error, most likely first.
+    ///
+    /// Suggestions are not generated automatically: see [`Rich::with_suggestions`].
+    pub fn suggestions(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.inner.suggestions.iter().map(|s| s.as_str())
+    }
+
+    /// Generate 'did you mean' suggestions for this error by comparing the text that was found against the patterns
+    /// that were expected.
+    ///
+    /// Expected tokens and labels are compared (using their [`fmt::Display`] implementations) against `found` by
+    /// [edit distance](https://en.wikipedia.org/wiki/Damerau%E2%80%93Levenshtein_distance), counting transposed
+    /// characters as a single edit. Those within `max_distance` edits are kept, closest first. Labelling keyword
+    /// parsers with their keyword is a good way to make them eligible for suggestions.
+    ///
+    /// Errors only track the first token that was found, so `found` is supplied by the caller. When parsing `&str`
+    /// inputs, this is usually the slice of the source covered by [`Rich::span`]. When parsing a token stream, it's
+    /// usually the displayed form of [`Rich::found`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// # use std::fmt;
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Token<'a> { Function, Let, Return, Ident(&'a str) }
+    ///
+    /// impl fmt::Display for Token<'_> {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         match self {
+    ///             Token::Function => write!(f, "function"),
+    ///             Token::Let => write!(f, "let"),
+    ///             Token::Return => write!(f, "return"),
+    ///             Token::Ident(ident) => write!(f, "{}", ident),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let stmt = choice((
+    ///     just::<_, _, extra::Err<Rich<_>>>(Token::Function),
+    ///     just(Token::Let),
+    ///     just(Token::Return),
+    /// ));
+    ///
+    /// let tokens = [Token::Ident("fucntion")];
+    /// let err = stmt.parse(&tokens).into_errors().remove(0);
+    /// let found = err.found().unwrap().to_string();
+    /// let err = err.with_suggestions(&found, 2);
+    ///
+    /// assert_eq!(err.suggestions().collect::<Vec<_>>(), vec!["function"]);
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "found 'fucntion' expected 'function', 'let', or 'return', did you mean 'function'?",
+    /// );
+    /// ```
+    pub fn with_suggestions(mut self, found: &str, max_distance: usize) -> Self
+    where
+        T: fmt::Display,
+        L: fmt::Display,
+    {
+        let mut candidates = self
+            .expected()
+            .filter_map(|pat| match pat {
+                RichPattern::Token(tok) => Some(tok.to_string()),
+                RichPattern::Label(label) => Some(label.to_string()),
+                RichPattern::Regex(_) | RichPattern::Kind(_) | RichPattern::EndOfInput => None,
+            })
+            .filter_map(|candidate| {
+                let dist = edit_distance(found, &candidate);
+                if dist > 0 && dist <= max_distance {
+                    Some((dist, candidate))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        // Stable, so candidates at the same distance keep the order in which they were expected
+        candidates.sort_by_key(|(dist, _)| *dist);
+        for (_, candidate) in candidates {
+            if !self.inner.suggestions.contains(&candidate) {
+                self.inner.suggestions.push(candidate);
+            }
+        }
+        self
+    }
+
     /// Transform this error's tokens using the given function.
     ///
     /// This is useful when you wish to combine errors from multiple compilation passes (lexing and parsing, say) where
@@ -614,9 +844,16 @@ impl<'a, T, S, L> Rich<'a, T, S, L> {
     where
         T: Clone,
     {
+        let RichInner {
+            reason,
+            suggestions,
+        } = *self.inner;
         Rich {
             span: self.span,
-            reason: Box::new(self.reason.map_token(f)),
+            inner: Box::new(RichInner {
+                reason: reason.map_token(f),
+                suggestions,
+            }),
             #[cfg(feature = "label")]
             context: self.context,
         }
@@ -636,7 +873,7 @@ where
     ) -> Self {
         Self {
             span,
-            reason: Box::new(RichReason::ExpectedFound {
+            inner: RichInner::boxed(RichReason::ExpectedFound {
                 expected: expected
                     .into_iter()
                     .map(|tok| {
@@ -651,14 +888,98 @@ where
         }
     }
 
+    #[inline]
+    fn too_many_errors(span: I::Span) -> Self {
+        Self::custom(span, "too many errors, parsing halted")
+    }
+
+    fn expected_tokens(&self) -> Vec<&MaybeRef<'a, I::Token>> {
+        self.expected()
+            .filter_map(|pat| match pat {
+                RichPattern::Token(tok) => Some(tok),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[inline]
+    fn unclosed_delimiter(self, open_span: I::Span) -> Self {
+        #[cfg_attr(not(feature = "label"), allow(unused_mut))]
+        let mut err = Self::custom(open_span, "unclosed delimiter");
+        #[cfg(feature = "label")]
+        {
+            err.context = self.context;
+        }
+        err
+    }
+
+    #[inline]
+    fn repaired(self, edit: recovery::Edit<'a, I::Token>, span: I::Span) -> Self {
+        Self {
+            span,
+            inner: RichInner::boxed(RichReason::Repaired(edit)),
+            #[cfg(feature = "label")]
+            context: self.context,
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    #[inline]
+    fn expected_regex(pattern: &str, found: Option<MaybeRef<'a, I::Token>>, span: I::Span) -> Self {
+        Self {
+            span,
+            inner: RichInner::boxed(RichReason::ExpectedFound {
+                expected: vec![RichPattern::Regex(pattern.into())],
+                found,
+            }),
+            #[cfg(feature = "label")]
+            context: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn expected_kinds(
+        expected: KindSet<I::Token>,
+        found: Option<MaybeRef<'a, I::Token>>,
+        span: I::Span,
+    ) -> Self
+    where
+        I::Token: TokenKind,
+    {
+        Self {
+            span,
+            inner: RichInner::boxed(RichReason::ExpectedFound {
+                expected: expected
+                    .iter()
+                    .map(|kind| RichPattern::Kind(I::Token::kind_name(kind)))
+                    .collect(),
+                found,
+            }),
+            #[cfg(feature = "label")]
+            context: Vec::new(),
+        }
+    }
+
     #[inline]
     fn merge(self, other: Self) -> Self {
-        let new_reason = self.reason.flat_merge(*other.reason);
+        let mut inner = self.inner;
+        let RichInner {
+            reason: other_reason,
+            suggestions: other_suggestions,
+        } = *other.inner;
+        // Reuse the existing allocation rather than making a new one
+        let reason = core::mem::replace(&mut inner.reason, RichReason::Many(Vec::new()));
+        inner.reason = reason.flat_merge(other_reason);
+        for s in other_suggestions {
+            if !inner.suggestions.contains(&s) {
+                inner.suggestions.push(s);
+            }
+        }
         Self {
             span: self.span,
-            reason: Box::new(new_reason),
+            inner,
             #[cfg(feature = "label")]
-            context: self.context, // TOOD: Merge contexts
+            context: merge_contexts(self.context, other.context),
         }
     }
 
@@ -669,14 +990,22 @@ where
         found: Option<MaybeRef<'a, I::Token>>,
         _span: I::Span,
     ) -> Self {
-        match &mut *self.reason {
+        match &mut self.inner.reason {
             RichReason::ExpectedFound { expected, found: _ } => {
                 for new_expected in new_expected {
-                    let new_expected = new_expected
-                        .map(RichPattern::Token)
-                        .unwrap_or(RichPattern::EndOfInput);
-                    if !expected[..].contains(&new_expected) {
-                        expected.push(new_expected);
+                    // This is very hot, so compare tokens directly rather than going through `RichPattern`'s
+                    // `PartialEq`, which is much slower
+                    let is_new = expected.iter().all(|pat| match (pat, &new_expected) {
+                        (RichPattern::Token(tok), Some(new_tok)) => **tok != **new_tok,
+                        (RichPattern::EndOfInput, None) => false,
+                        _ => true,
+                    });
+                    if is_new {
+                        expected.push(
+                            new_expected
+                                .map(RichPattern::Token)
+                                .unwrap_or(RichPattern::EndOfInput),
+                        );
                     }
                 }
             }
@@ -690,9 +1019,9 @@ where
                     .collect(),
                 found,
             }),
-            RichReason::Custom(_) => {
-                let old = core::mem::replace(&mut *self.reason, RichReason::Many(Vec::new()));
-                self.reason = Box::new(RichReason::Many(vec![
+            RichReason::Custom(_) | RichReason::Repaired(_) => {
+                let old = core::mem::replace(&mut self.inner.reason, RichReason::Many(Vec::new()));
+                self.inner.reason = RichReason::Many(vec![
                     old,
                     RichReason::ExpectedFound {
                         expected: new_expected
@@ -704,10 +1033,10 @@ where
                             .collect(),
                         found,
                     },
-                ]));
+                ]);
             }
         }
-        // TOOD: Merge contexts
+        // The new expected/found pair doesn't carry any contexts of its own, so there's nothing to merge
         self
     }
 
@@ -719,7 +1048,7 @@ where
         span: I::Span,
     ) -> Self {
         self.span = span;
-        match &mut *self.reason {
+        match &mut self.inner.reason {
             RichReason::ExpectedFound { expected, found } => {
                 expected.clear();
                 expected.extend(new_expected.into_iter().map(|tok| {
@@ -729,7 +1058,7 @@ where
                 *found = new_found;
             }
             _ => {
-                self.reason = Box::new(RichReason::ExpectedFound {
+                self.inner.reason = RichReason::ExpectedFound {
                     expected: new_expected
                         .into_iter()
                         .map(|tok| {
@@ -738,11 +1067,12 @@ where
                         })
                         .collect(),
                     found: new_found,
-                });
+                };
             }
         }
         #[cfg(feature = "label")]
         self.context.clear();
+        self.inner.suggestions.clear();
         self
     }
 }
@@ -756,16 +1086,16 @@ where
     #[inline]
     fn label_with(&mut self, label: L) {
         // Opportunistically attempt to reuse allocations if we can
-        match &mut *self.reason {
+        match &mut self.inner.reason {
             RichReason::ExpectedFound { expected, found: _ } => {
                 expected.clear();
                 expected.push(RichPattern::Label(label));
             }
             _ => {
-                self.reason = Box::new(RichReason::ExpectedFound {
+                self.inner.reason = RichReason::ExpectedFound {
                     expected: vec![RichPattern::Label(label)],
-                    found: self.reason.take_found(),
-                });
+                    found: self.inner.reason.take_found(),
+                };
             }
         }
     }
@@ -778,6 +1108,50 @@ where
     }
 }
 
+/// The optimal string alignment distance between two strings: the number of character insertions, deletions,
+/// substitutions, or transpositions of adjacent characters required to turn one into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    // Only the last two rows of the table are required at any one time
+    let mut prev2 = vec![0; b.len() + 1];
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut cur = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        cur[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(prev2[j - 2] + 1);
+            }
+        }
+        core::mem::swap(&mut prev2, &mut prev);
+        core::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Merge the context stacks of two errors that occurred at the same location.
+///
+/// Contexts are stored from least general to most, so contexts common to both stacks (i.e: the outer parsers that both
+/// alternatives were being parsed within) appear at the end of each. These are kept once, while contexts unique to
+/// either stack are placed before them: first those of `this`, then those of `other`.
+#[cfg(feature = "label")]
+fn merge_contexts<L: PartialEq, S>(mut this: Vec<(L, S)>, mut other: Vec<(L, S)>) -> Vec<(L, S)> {
+    let common = this
+        .iter()
+        .rev()
+        .zip(other.iter().rev())
+        .take_while(|((a, _), (b, _))| a == b)
+        .count();
+    other.truncate(other.len() - common);
+    other.retain(|(l, _)| this.iter().all(|(this_l, _)| this_l != l));
+    let divergent_end = this.len() - common;
+    this.splice(divergent_end..divergent_end, other);
+    this
+}
+
 impl<'a, T, S, L> fmt::Debug for Rich<'a, T, S, L>
 where
     T: fmt::Debug,
@@ -814,3 +1188,40 @@ fn write_token<T>(
         None => write!(f, "end of input"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("let", ""), 3);
+        assert_eq!(edit_distance("fucntion", "function"), 1);
+        assert_eq!(edit_distance("retrun", "return"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("λx", "λy"), 1);
+    }
+
+    #[test]
+    fn suggestion_threshold() {
+        let parser = choice((
+            just::<_, _, extra::Err<Rich<&str>>>("while"),
+            just("for"),
+            just("loop"),
+            just("fork"),
+        ));
+        let tokens = ["fro"];
+        let err = parser.parse(&tokens).into_errors().remove(0);
+
+        let close = err.clone().with_suggestions("fro", 1);
+        assert_eq!(close.suggestions().collect::<Vec<_>>(), vec!["for"]);
+
+        let far = err.with_suggestions("fro", 2);
+        assert_eq!(far.suggestions().collect::<Vec<_>>(), vec!["for", "fork"]);
+        assert_eq!(
+            far.to_string(),
+            "found 'fro' expected 'while', 'for', 'loop', or 'fork', did you mean 'for', or 'fork'?",
+        );
+    }
+}
@@ -12,6 +12,8 @@
 //!
 //! The extension API is versioned. See the [`v1`] module for the current implementation of the API.
 //!
+//! Extensions may be parsers (see [`v1::ExtParser`]) or error recovery strategies (see [`v1::ExtStrategy`]).
+//!
 //! # Example
 //!
 //! ```
@@ -69,11 +71,12 @@ use super::*;
 /// Versioning the extension API allows us to make significant changes to it in the future without breaking crates that
 /// depend on it.
 pub mod v1 {
-    pub use super::current::{Ext, ExtParser};
+    pub use super::current::{Ext, ExtParser, ExtStrategy, Recovery};
 }
 
 mod current {
     use super::*;
+    use crate::input::Marker;
 
     /// A trait implemented by extension parsers.
     ///
@@ -164,4 +167,195 @@ mod current {
 
         go_extra!(O);
     }
+    /// A trait implemented by extension error recovery strategies.
+    ///
+    /// Implement this trait, and chumsky will automatically make [`Ext<YourStrategy>`] implement [`Strategy`], allowing
+    /// it to be used with [`Parser::recover_with`].
+    ///
+    /// Like [`ExtParser`], this trait is a stable interface that does not expose the inner workings of chumsky's error
+    /// handling. Instead, strategies interact with the failed parse through [`Recovery`].
+    ///
+    /// # Example
+    ///
+    /// A strategy that skips up to a given number of tokens, retrying the parser after each one.
+    ///
+    /// ```
+    /// use chumsky::{prelude::*, input::ValueInput, extension::v1::{Ext, ExtStrategy, Recovery}};
+    ///
+    /// pub struct SkipUpTo_(usize);
+    ///
+    /// impl<'a, I, O, E> ExtStrategy<'a, I, O, E> for SkipUpTo_
+    /// where
+    ///     I: ValueInput<'a>,
+    ///     E: extra::ParserExtra<'a, I>,
+    /// {
+    ///     fn recover<P: Parser<'a, I, O, E>>(&self, rec: &mut Recovery<'a, '_, '_, I, E>, parser: &P) -> Option<O> {
+    ///         for _ in 0..self.0 {
+    ///             // Give up at the end of the input
+    ///             rec.input().next()?;
+    ///             if let Some(out) = rec.retry(parser) {
+    ///                 return Some(out);
+    ///             }
+    ///         }
+    ///         None
+    ///     }
+    /// }
+    ///
+    /// pub fn skip_up_to(n: usize) -> Ext<SkipUpTo_> {
+    ///     Ext(SkipUpTo_(n))
+    /// }
+    ///
+    /// let num = text::int::<_, _, extra::Err<Rich<char>>>(10).recover_with(skip_up_to(2));
+    /// let nums = num.separated_by(just(',')).collect::<Vec<_>>();
+    ///
+    /// let (out, errs) = nums.parse("1,??2,3").into_output_errors();
+    /// assert_eq!(out, Some(vec!["1", "2", "3"]));
+    /// assert_eq!(errs.len(), 1);
+    ///
+    /// // Three tokens is too many to skip
+    /// assert!(nums.parse("1,???2,3").has_errors());
+    /// ```
+    pub trait ExtStrategy<'a, I: Input<'a>, O, E: ParserExtra<'a, I>> {
+        /// Attempt to recover from a failure of the given parser.
+        ///
+        /// When called, the input is positioned where the parser began (i.e: before the error occurred). Returning
+        /// `Some(output)` indicates that recovery was successful: the original error will be emitted as a secondary
+        /// error and parsing will continue from the current position of the input. Returning `None` indicates that
+        /// recovery failed: the input will be rewound and the original error will be restored, allowing other parsers
+        /// to be attempted.
+        fn recover<P: Parser<'a, I, O, E>>(
+            &self,
+            rec: &mut Recovery<'a, '_, '_, I, E>,
+            parser: &P,
+        ) -> Option<O>;
+    }
+
+    /// The state of an error recovery attempt, given to [`ExtStrategy::recover`].
+    pub struct Recovery<'a, 'parse, 'r, I: Input<'a>, E: ParserExtra<'a, I>> {
+        inp: &'r mut InputRef<'a, 'parse, I, E>,
+        error: E::Error,
+        start: Marker<'a, 'parse, I>,
+    }
+
+    impl<'a, 'parse, 'r, I: Input<'a>, E: ParserExtra<'a, I>> Recovery<'a, 'parse, 'r, I, E> {
+        /// Get the input that is being recovered.
+        pub fn input(&mut self) -> &mut InputRef<'a, 'parse, I, E> {
+            self.inp
+        }
+
+        /// Get the error that the parser produced when it failed.
+        pub fn error(&self) -> &E::Error {
+            &self.error
+        }
+
+        /// Rewind the input to the position at which the parser began, discarding any secondary errors emitted since.
+        pub fn rewind(&mut self) {
+            self.inp.rewind(self.start);
+        }
+
+        /// Emit an additional secondary error at the current position of the input.
+        ///
+        /// The original error is emitted automatically when recovery succeeds, so there is no need to emit it here.
+        pub fn emit(&mut self, error: E::Error) {
+            self.inp.emit(self.inp.offset, error);
+        }
+
+        /// Try the given parser (usually the parser being recovered) at the current position of the input.
+        ///
+        /// Retrying is only considered successful if the parser succeeds without emitting any secondary errors. If
+        /// unsuccessful, the input is left unchanged.
+        pub fn retry<O, P: Parser<'a, I, O, E>>(&mut self, parser: &P) -> Option<O> {
+            let before = self.inp.save();
+            let old_alt = self.inp.errors.alt.take();
+            let out = parser.go::<Emit>(self.inp).ok().filter(|_| {
+                self.inp
+                    .errors
+                    .secondary_errors_since(before.err_count)
+                    .is_empty()
+            });
+            self.inp.errors.alt = old_alt;
+            if out.is_none() {
+                self.inp.rewind(before);
+            }
+            out
+        }
+    }
+
+    impl<T> Sealed for Ext<T> {}
+    impl<'a, I, O, E, S> Strategy<'a, I, O, E> for Ext<S>
+    where
+        I: Input<'a>,
+        E: ParserExtra<'a, I>,
+        S: ExtStrategy<'a, I, O, E>,
+    {
+        fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+            &self,
+            inp: &mut InputRef<'a, '_, I, E>,
+            parser: &P,
+        ) -> PResult<M, O> {
+            let alt = inp.errors.alt.take().expect("error but no alt?");
+            let mut rec = Recovery {
+                start: inp.save(),
+                inp,
+                error: alt.err,
+            };
+            match self.0.recover(&mut rec, parser) {
+                Some(out) => {
+                    let Recovery { inp, error, .. } = rec;
+                    inp.emit(inp.offset, error);
+                    Ok(M::bind(|| out))
+                }
+                None => {
+                    let Recovery { inp, error, start } = rec;
+                    inp.rewind(start);
+                    inp.errors.alt = Some(Located::at(alt.pos, error));
+                    Err(())
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::v1::{Ext, ExtStrategy, Recovery};
+    use crate::prelude::*;
+
+    // Produces a default output, having rewound any input consumed by the failed parser
+    struct Default_;
+
+    impl<'a, I, O, E> ExtStrategy<'a, I, O, E> for Default_
+    where
+        I: Input<'a>,
+        O: Default,
+        E: extra::ParserExtra<'a, I>,
+        E::Error: Clone,
+    {
+        fn recover<P: Parser<'a, I, O, E>>(
+            &self,
+            rec: &mut Recovery<'a, '_, '_, I, E>,
+            parser: &P,
+        ) -> Option<O> {
+            // Retrying at the same position won't help
+            assert!(rec.retry(parser).is_none());
+            rec.rewind();
+            let err = rec.error().clone();
+            rec.emit(err);
+            Some(O::default())
+        }
+    }
+
+    #[test]
+    fn ext_strategy() {
+        let parser = just::<_, _, extra::Err<Rich<char>>>("ab")
+            .to(1)
+            .recover_with(Ext(Default_))
+            .then(any().repeated().count());
+
+        let (out, errs) = parser.parse("ac").into_output_errors();
+        assert_eq!(out, Some((0, 2)));
+        // The strategy emitted the error too
+        assert_eq!(errs.len(), 2);
+        assert_eq!(errs[0], errs[1]);
+    }
 }
@@ -0,0 +1,414 @@
+//! Generation of random inputs from parsers, for use in fuzzing and property tests.
+//!
+//! A [`Generator`] walks the same tree of combinators that a parser is built from, making random choices along the
+//! way (which alternative of a [`choice`] to take, how many times to run a [`Parser::repeated`] pattern, etc.) and
+//! producing the tokens that the parser would accept. Randomness is drawn from an [`arbitrary::Unstructured`], so
+//! generators slot neatly into fuzz targets, and [`strategy`] (requires the `proptest` feature) integrates them with
+//! `proptest`.
+//!
+//! Generated inputs can also be [mutated](Generator::generate_mutated) into 'near misses': inputs that are almost, but
+//! probably not quite, valid. These are useful for exercising error recovery.
+//!
+//! # Limitations
+//!
+//! Inputs are generated from the structure of the parser alone, so they are not *guaranteed* to be accepted by it.
+//! In particular:
+//!
+//! - [`choice`] and [`Parser::or`] are ordered, so an input generated for a later alternative might be accepted by
+//!   an earlier one, and adjacent patterns such as two identifiers may run into one-another unless separated.
+//!
+//! - Patterns that depend on the values they parse cannot be generated. These include [`Parser::try_map`],
+//!   [`Parser::and_is`], [`Parser::rewind`], [`custom`], [`select!`], and context-sensitive parsers. Attempting to
+//!   generate an input for them produces [`GenerateError::Unsupported`].
+//!
+//! - [`Parser::filter`] is supported only when applied directly to single-token patterns such as [`any`] or
+//!   [`one_of`], and works by repeatedly generating tokens until one satisfies the filter. When generating text, it
+//!   helps to [restrict the tokens](Generator::with_tokens) that are generated to a suitable subset.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, generate::Generator};
+//! let list = text::int::<_, _, extra::Err<Simple<char>>>(10)
+//!     .separated_by(just(','))
+//!     .at_least(1)
+//!     .collect::<Vec<_>>()
+//!     .delimited_by(just('['), just(']'));
+//!
+//! // In practice, this would come from a fuzzer or a property testing framework
+//! let data = [23, 5, 127, 42, 200, 1, 99, 17, 64, 8, 31, 240];
+//! let input = Generator::with_tokens(&data, |u| Ok(u.int_in_range(b' '..=b'~')? as char))
+//!     .generate(&list)
+//!     .unwrap()
+//!     .into_iter()
+//!     .collect::<String>();
+//!
+//! assert!(input.starts_with('[') && input.ends_with(']'));
+//! assert!(list.parse(input.as_str()).has_output());
+//! ```
+
+use super::*;
+use arbitrary::{Arbitrary, Unstructured};
+
+/// The number of attempts made to generate a token that satisfies a filter before giving up.
+const MAX_ATTEMPTS: usize = 256;
+
+/// An error that occurred while generating an input. See [`Generator::generate`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GenerateError {
+    /// The parser contains a pattern for which inputs cannot be generated. Contains the name of its type.
+    Unsupported(&'static str),
+    /// Recursion exceeded the generator's maximum depth and no non-recursive alternative could be found.
+    TooDeep,
+    /// No token satisfying a filter could be found.
+    Exhausted,
+    /// Drawing from the source of randomness failed.
+    Arbitrary(arbitrary::Error),
+}
+
+impl From<arbitrary::Error> for GenerateError {
+    fn from(err: arbitrary::Error) -> Self {
+        Self::Arbitrary(err)
+    }
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unsupported(name) => write!(f, "cannot generate inputs for '{}'", name),
+            Self::TooDeep => write!(f, "exceeded maximum recursion depth"),
+            Self::Exhausted => write!(f, "could not generate a token that satisfies a filter"),
+            Self::Arbitrary(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GenerateError {}
+
+/// Generates random inputs from parsers. See the [module-level documentation](self) for more information.
+pub struct Generator<'u, T> {
+    u: Unstructured<'u>,
+    token: fn(&mut Unstructured<'_>) -> arbitrary::Result<T>,
+    tokens: Vec<T>,
+    depth: usize,
+    max_depth: usize,
+    max_repeats: usize,
+    // State of a fallback PRNG, used to keep generating varied tokens once `u` runs dry
+    seed: u64,
+}
+
+impl<'u, T> Generator<'u, T> {
+    /// Create a generator that draws randomness from the given data, generating tokens with [`Arbitrary`].
+    pub fn new(data: &'u [u8]) -> Self
+    where
+        T: for<'x> Arbitrary<'x>,
+    {
+        Self::with_tokens(data, |u| T::arbitrary(u))
+    }
+
+    /// Create a generator that draws randomness from the given data, generating tokens with the given function.
+    ///
+    /// Tokens are generated for patterns that accept many different tokens, such as [`any`] and [`none_of`]. Limiting
+    /// the tokens generated (for example, to printable ASCII characters) generally produces more useful inputs.
+    pub fn with_tokens(
+        data: &'u [u8],
+        token: fn(&mut Unstructured<'_>) -> arbitrary::Result<T>,
+    ) -> Self {
+        Self {
+            u: Unstructured::new(data),
+            token,
+            tokens: Vec::new(),
+            depth: 0,
+            max_depth: 16,
+            max_repeats: 4,
+            seed: data.iter().fold(0x9E37_79B9_7F4A_7C15, |seed, b| {
+                (seed ^ *b as u64).rotate_left(5)
+            }),
+        }
+    }
+
+    /// Set the maximum depth to which [`Recursive`] parsers may nest (defaults to 16).
+    pub fn max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    /// Set the maximum number of extra repetitions generated for repeating patterns beyond the minimum they require
+    /// (defaults to 4).
+    pub fn max_repeats(self, max_repeats: usize) -> Self {
+        Self {
+            max_repeats,
+            ..self
+        }
+    }
+
+    /// Generate an input that the given parser should accept.
+    ///
+    /// A single generator may be used to generate many inputs, each drawing further randomness from the data it was
+    /// created with.
+    pub fn generate<'a, I, O, E, P>(&mut self, parser: &P) -> Result<Vec<T>, GenerateError>
+    where
+        I: Input<'a, Token = T>,
+        E: ParserExtra<'a, I>,
+        P: Parser<'a, I, O, E>,
+        T: Clone,
+    {
+        self.tokens.clear();
+        self.depth = 0;
+        parser.generate(self)?;
+        Ok(core::mem::take(&mut self.tokens))
+    }
+
+    /// Generate an input that the given parser should accept, then apply a few random edits to it (inserting,
+    /// deleting, duplicating or swapping tokens) to produce an input that the parser will likely reject.
+    pub fn generate_mutated<'a, I, O, E, P>(&mut self, parser: &P) -> Result<Vec<T>, GenerateError>
+    where
+        I: Input<'a, Token = T>,
+        E: ParserExtra<'a, I>,
+        P: Parser<'a, I, O, E>,
+        T: Clone,
+    {
+        let mut tokens = self.generate(parser)?;
+        for _ in 0..self.u.int_in_range(1..=3)? {
+            match self.u.choose_index(4)? {
+                0 | 1 if tokens.is_empty() => tokens.push(self.token()?),
+                0 => {
+                    let tok = self.token()?;
+                    tokens.insert(self.u.choose_index(tokens.len() + 1)?, tok);
+                }
+                1 => {
+                    tokens.remove(self.u.choose_index(tokens.len())?);
+                }
+                2 if !tokens.is_empty() => {
+                    let idx = self.u.choose_index(tokens.len())?;
+                    tokens.insert(idx, tokens[idx].clone());
+                }
+                3 if tokens.len() > 1 => {
+                    let idx = self.u.choose_index(tokens.len() - 1)?;
+                    tokens.swap(idx, idx + 1);
+                }
+                _ => tokens.push(self.token()?),
+            }
+        }
+        Ok(tokens)
+    }
+
+    /// Generate a single arbitrary token.
+    pub(crate) fn token(&mut self) -> Result<T, GenerateError> {
+        if self.u.is_empty() {
+            // Keep producing varied tokens so that filters can still be satisfied
+            self.seed ^= self.seed << 13;
+            self.seed ^= self.seed >> 7;
+            self.seed ^= self.seed << 17;
+            let bytes = self.seed.to_le_bytes();
+            Ok((self.token)(&mut Unstructured::new(&bytes))?)
+        } else {
+            Ok((self.token)(&mut self.u)?)
+        }
+    }
+
+    /// Generate a single arbitrary token that satisfies the given predicate.
+    pub(crate) fn token_where(&mut self, f: impl Fn(&T) -> bool) -> Result<T, GenerateError> {
+        for _ in 0..MAX_ATTEMPTS {
+            let tok = self.token()?;
+            if f(&tok) {
+                return Ok(tok);
+            }
+        }
+        Err(GenerateError::Exhausted)
+    }
+
+    /// Append a token to the input being generated.
+    pub(crate) fn push(&mut self, tok: T) {
+        self.tokens.push(tok);
+    }
+
+    /// Choose an index in `0..len`.
+    pub(crate) fn choose(&mut self, len: usize) -> Result<usize, GenerateError> {
+        Ok(self.u.choose_index(len)?)
+    }
+
+    /// Flip a coin.
+    pub(crate) fn flip(&mut self) -> Result<bool, GenerateError> {
+        Ok(self.u.arbitrary()?)
+    }
+
+    /// Choose how many times a repeating pattern should repeat.
+    pub(crate) fn repeats(
+        &mut self,
+        at_least: usize,
+        at_most: u64,
+    ) -> Result<usize, GenerateError> {
+        let at_most = (at_least.saturating_add(self.max_repeats) as u64).min(at_most) as usize;
+        if at_least > at_most {
+            return Err(GenerateError::Exhausted);
+        }
+        Ok(self.u.int_in_range(at_least..=at_most)?)
+    }
+
+    /// Generate a pattern one level of recursion deeper, failing if the maximum depth has been reached.
+    pub(crate) fn nested(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<(), GenerateError>,
+    ) -> Result<(), GenerateError> {
+        if self.depth >= self.max_depth {
+            return Err(GenerateError::TooDeep);
+        }
+        self.depth += 1;
+        let res = f(self);
+        self.depth -= 1;
+        res
+    }
+
+    /// Generate one of `n` alternative patterns, chosen at random. If the chosen alternative cannot be generated,
+    /// the others are tried in turn.
+    pub(crate) fn alternatives(
+        &mut self,
+        n: usize,
+        f: impl Fn(&mut Self, usize) -> Result<(), GenerateError>,
+    ) -> Result<(), GenerateError> {
+        let len = self.tokens.len();
+        let start = self.choose(n)?;
+        let mut res = Err(GenerateError::Exhausted);
+        for i in 0..n {
+            self.tokens.truncate(len);
+            res = f(self, (start + i) % n);
+            if res.is_ok() {
+                break;
+            }
+        }
+        res
+    }
+}
+
+/// Create a `proptest` strategy that produces inputs generated from the given parser, using the given function to
+/// generate tokens (see [`Generator::with_tokens`]).
+///
+/// Inputs that cannot be generated (for example, because the parser contains unsupported patterns) are rejected
+/// rather than causing a test failure, so it's worth checking that a [`Generator`] works for your parser first.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, generate::strategy};
+/// use proptest::prelude::*;
+///
+/// fn ident<'a>() -> impl Parser<'a, &'a str, &'a str, extra::Err<Simple<'a, char>>> {
+///     text::ident()
+/// }
+///
+/// proptest!(|(input in strategy(ident(), |u| Ok(u.int_in_range(b' '..=b'~')? as char)))| {
+///     let input = input.into_iter().collect::<String>();
+///     prop_assert_eq!(ident().parse(input.as_str()).into_result(), Ok(input.as_str()));
+/// });
+/// ```
+#[cfg(feature = "proptest")]
+pub fn strategy<'a, I, O, E, P>(
+    parser: P,
+    token: fn(&mut Unstructured<'_>) -> arbitrary::Result<I::Token>,
+) -> impl proptest::strategy::Strategy<Value = Vec<I::Token>>
+where
+    I: Input<'a>,
+    I::Token: Clone + fmt::Debug,
+    E: ParserExtra<'a, I>,
+    P: Parser<'a, I, O, E>,
+{
+    use proptest::{collection::vec, prelude::any, strategy::Strategy};
+
+    vec(any::<u8>(), 0..256).prop_filter_map("could not generate an input", move |data| {
+        Generator::with_tokens(&data, token).generate(&parser).ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ascii(u: &mut Unstructured<'_>) -> arbitrary::Result<char> {
+        Ok(u.int_in_range(b' '..=b'~')? as char)
+    }
+
+    // Deterministic pseudo-random data, so that test failures are reproducible
+    fn data(seed: u64) -> Vec<u8> {
+        let mut state = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+        (0..64)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 56) as u8
+            })
+            .collect()
+    }
+
+    fn expr<'a>() -> impl Parser<'a, &'a str, (), extra::Err<Simple<'a, char>>> + Clone {
+        recursive(|expr| {
+            let call = text::ident().then(
+                expr.clone()
+                    .separated_by(just(','))
+                    .allow_trailing()
+                    .collect::<Vec<_>>()
+                    .delimited_by(just('['), just(']')),
+            );
+            // Calls must come before identifiers, since choice is ordered
+            let atom = choice((
+                text::int(10).ignored(),
+                call.ignored(),
+                text::ident().ignored(),
+                expr.delimited_by(just('('), just(')')),
+            ))
+            .padded();
+            atom.clone()
+                .foldl(one_of("+-*/").then(atom).repeated(), |_, _| ())
+        })
+        .then_ignore(end())
+    }
+
+    #[test]
+    fn generated_inputs_parse() {
+        for seed in 0..500 {
+            let data = data(seed);
+            let input = Generator::with_tokens(&data, ascii)
+                .max_depth(4)
+                .generate(&expr())
+                .unwrap()
+                .into_iter()
+                .collect::<String>();
+            assert_eq!(
+                expr().parse(input.as_str()).into_result(),
+                Ok(()),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn mutated_inputs() {
+        fn parser<'a>() -> impl Parser<'a, &'a str, (), extra::Err<Simple<'a, char>>> {
+            one_of("abc").repeated().exactly(3).then_ignore(end())
+        }
+        let mut rejected = 0;
+        for seed in 0..100 {
+            let data = data(seed);
+            let input = Generator::with_tokens(&data, ascii)
+                .generate_mutated(&parser())
+                .unwrap()
+                .into_iter()
+                .collect::<String>();
+            rejected += parser().parse(input.as_str()).has_errors() as usize;
+        }
+        // Some mutations (such as swapping identical tokens) produce valid inputs, but most should not
+        assert!(rejected > 50);
+    }
+
+    #[test]
+    fn unsupported() {
+        let parser = any::<&str, extra::Default>().rewind();
+        assert!(matches!(
+            Generator::with_tokens(&[], ascii).generate(&parser),
+            Err(GenerateError::Unsupported(name)) if name.contains("Rewind"),
+        ));
+    }
+}
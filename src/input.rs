@@ -61,6 +61,13 @@ pub trait Input<'a>: Sealed + 'a {
     #[doc(hidden)]
     fn prev(offs: Self::Offset) -> Self::Offset;
 
+    /// Get the tokens that the input skipped over because they were invalid (such as the error tokens produced by a
+    /// lexer), along with their offsets and spans. These are reported as errors when parsing begins.
+    #[doc(hidden)]
+    fn invalid_tokens(&self) -> Vec<(Self::Offset, Self::Token, Self::Span)> {
+        Vec::new()
+    }
+
     /// Split an input that produces tokens of type `(T, S)` into one that produces tokens of type `T` and spans of
     /// type `S`.
     ///
@@ -105,6 +112,91 @@ pub trait Input<'a>: Sealed + 'a {
             context,
         }
     }
+
+    /// Translate spans generated by this input into the coordinates of another input: offsets are shifted by the
+    /// start of `base`, and spans take on its context.
+    ///
+    /// This is useful when this input was extracted from a larger one, such as the contents of a string literal
+    /// token, and spans should point into the original file. See [`Parser::nested_in_with`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let word = text::ident::<_, _, extra::Default>()
+    ///     .map_with_span(|_, span: SimpleSpan| span);
+    ///
+    /// let input = "foo".rebase(SimpleSpan::from(10..13));
+    /// assert_eq!(word.parse(input).into_result(), Ok(SimpleSpan::from(10..13)));
+    /// ```
+    fn rebase<S>(self, base: S) -> Rebased<S, Self>
+    where
+        Self: Sized,
+        S: Span<Offset = usize> + Clone,
+        Self::Span: Span<Offset = usize, Context = ()>,
+    {
+        Rebased { input: self, base }
+    }
+
+    /// Skip over tokens for which the given function returns `true`, such as whitespace or comments, without copying
+    /// the input.
+    ///
+    /// Skipped tokens are never seen by parsers. Spans and slices are those of the original input, but do not begin
+    /// with skipped tokens: the skipped tokens within a construct (or their spans) can still be recovered by slicing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Token {
+    ///     Num(u64),
+    ///     Plus,
+    ///     Comment,
+    /// }
+    ///
+    /// let tokens = [
+    ///     (Token::Comment, SimpleSpan::from(0..5)),
+    ///     (Token::Num(1), SimpleSpan::from(6..7)),
+    ///     (Token::Plus, SimpleSpan::from(8..9)),
+    ///     (Token::Comment, SimpleSpan::from(10..15)),
+    ///     (Token::Num(2), SimpleSpan::from(16..17)),
+    /// ];
+    ///
+    /// let is_comment = |tok: &Token| *tok == Token::Comment;
+    /// let num = select! { Token::Num(x) => x };
+    /// let sum = num
+    ///     .then_ignore(just::<_, _, extra::Default>(Token::Plus))
+    ///     .then(num);
+    ///
+    /// let input = tokens.spanned((17..17).into()).skip_tokens(is_comment);
+    /// assert_eq!(
+    ///     sum.clone().map(|(a, b)| a + b).parse(input).into_result(),
+    ///     Ok(3),
+    /// );
+    ///
+    /// // The span of the sum doesn't include the leading comment, but does include the comment within it
+    /// let input = tokens.spanned((17..17).into()).skip_tokens(is_comment);
+    /// assert_eq!(
+    ///     sum.clone()
+    ///         .map_with_span(|_, span: SimpleSpan| span).parse(input).into_result(),
+    ///     Ok(SimpleSpan::from(6..17)),
+    /// );
+    ///
+    /// // Slices are of the original input
+    /// let input = tokens.spanned((17..17).into()).skip_tokens(is_comment);
+    /// assert_eq!(
+    ///     sum.slice().parse(input).into_result(),
+    ///     Ok(&tokens[1..]),
+    /// );
+    /// ```
+    fn skip_tokens<F>(self, skip: F) -> SkipTokens<F, Self>
+    where
+        Self: Sized,
+        F: Fn(&Self::Token) -> bool,
+    {
+        SkipTokens { input: self, skip }
+    }
 }
 
 /// Implement by inputs that have a known size (including spans)
@@ -133,10 +225,65 @@ pub trait SliceInput<'a>: ExactSizeInput<'a> {
 // Implemented by inputs that reference a string slice and use byte indices as their offset.
 /// A trait for types that represent string-like streams of input tokens
 pub trait StrInput<'a, C: Char>:
-    ValueInput<'a, Offset = usize, Token = C> + SliceInput<'a, Slice = &'a C::Str>
+    ValueInput<'a, Offset = usize, Token = C>
+    + SliceInput<'a, Slice = &'a C::Str>
+    + TextInput<'a, C, Text = &'a C::Str>
 {
 }
 
+/// A trait for inputs made up of characters, allowing text parsers (such as [`text::ident`]) to operate on them.
+///
+/// Unlike [`StrInput`], the input need not be stored contiguously in memory: a [`Stream`] of [`char`]s is a valid
+/// text input too. Text parsers output the text they matched as [`TextInput::Text`], which is a slice of the input
+/// where possible and an owned string (see [`Char::Owned`]) otherwise.
+pub trait TextInput<'a, C: Char>: ValueInput<'a, Token = C> {
+    /// The type of text matched by text parsers.
+    ///
+    /// For inputs that implement [`StrInput`], this is `&'a C::Str`.
+    type Text: Borrow<C::Str>;
+
+    /// Get the text between a start and end offset.
+    ///
+    /// # Safety
+    ///
+    /// `range` must be made up of offsets generated by either `Input::start` or `ValueInput::next`.
+    #[doc(hidden)]
+    unsafe fn text(&self, range: Range<Self::Offset>) -> Self::Text;
+
+    /// Get the remainder of the input after the given offset, if the input is stored contiguously.
+    #[doc(hidden)]
+    #[inline(always)]
+    fn trailing_str(&self, _offset: Self::Offset) -> Option<&'a C::Str> {
+        None
+    }
+
+    /// Advance the given offset by a number of bytes of the string returned by `TextInput::trailing_str`.
+    #[doc(hidden)]
+    #[inline(always)]
+    fn skip_bytes(_offset: Self::Offset, _bytes: usize) -> Self::Offset {
+        unreachable!("input is not stored contiguously")
+    }
+}
+
+// Collect the tokens between two offsets into an owned string, for inputs that can't be sliced
+unsafe fn collect_text<'a, C: Char, I: ValueInput<'a, Token = C>>(
+    input: &I,
+    range: Range<I::Offset>,
+) -> C::Owned {
+    let mut offset = range.start;
+    core::iter::from_fn(|| {
+        if offset < range.end {
+            // SAFETY: the caller guarantees that `range.start` was generated by a call to `ValueInput::next`
+            let (next, tok) = unsafe { input.next(offset) };
+            offset = next;
+            tok
+        } else {
+            None
+        }
+    })
+    .collect()
+}
+
 /// Implemented by inputs that can have tokens borrowed from them.
 pub trait ValueInput<'a>: Input<'a> {
     /// Get the next offset from the provided one, and the next token if it exists
@@ -216,6 +363,25 @@ impl<'a> ValueInput<'a> for &'a str {
 
 impl<'a> StrInput<'a, char> for &'a str {}
 
+impl<'a> TextInput<'a, char> for &'a str {
+    type Text = &'a str;
+
+    #[inline(always)]
+    unsafe fn text(&self, range: Range<Self::Offset>) -> Self::Text {
+        <Self as SliceInput>::slice(self, range)
+    }
+
+    #[inline(always)]
+    fn trailing_str(&self, offset: Self::Offset) -> Option<&'a str> {
+        Some(<Self as SliceInput>::slice_from(self, offset..))
+    }
+
+    #[inline(always)]
+    fn skip_bytes(offset: Self::Offset, bytes: usize) -> Self::Offset {
+        offset + bytes
+    }
+}
+
 impl<'a> SliceInput<'a> for &'a str {
     type Slice = &'a str;
 
@@ -268,6 +434,34 @@ impl<'a, T> ExactSizeInput<'a> for &'a [T] {
 
 impl<'a> StrInput<'a, u8> for &'a [u8] {}
 
+impl<'a> TextInput<'a, u8> for &'a [u8] {
+    type Text = &'a [u8];
+
+    #[inline(always)]
+    unsafe fn text(&self, range: Range<Self::Offset>) -> Self::Text {
+        <Self as SliceInput>::slice(self, range)
+    }
+
+    #[inline(always)]
+    fn trailing_str(&self, offset: Self::Offset) -> Option<&'a [u8]> {
+        Some(<Self as SliceInput>::slice_from(self, offset..))
+    }
+
+    #[inline(always)]
+    fn skip_bytes(offset: Self::Offset, bytes: usize) -> Self::Offset {
+        offset + bytes
+    }
+}
+
+impl<'a> TextInput<'a, char> for &'a [char] {
+    type Text = String;
+
+    #[inline(always)]
+    unsafe fn text(&self, range: Range<Self::Offset>) -> Self::Text {
+        self[range].iter().collect()
+    }
+}
+
 impl<'a, T> SliceInput<'a> for &'a [T] {
     type Slice = &'a [T];
 
@@ -342,6 +536,25 @@ impl<'a, T: 'a, const N: usize> ExactSizeInput<'a> for &'a [T; N] {
 
 impl<'a, const N: usize> StrInput<'a, u8> for &'a [u8; N] {}
 
+impl<'a, const N: usize> TextInput<'a, u8> for &'a [u8; N] {
+    type Text = &'a [u8];
+
+    #[inline(always)]
+    unsafe fn text(&self, range: Range<Self::Offset>) -> Self::Text {
+        <Self as SliceInput>::slice(self, range)
+    }
+
+    #[inline(always)]
+    fn trailing_str(&self, offset: Self::Offset) -> Option<&'a [u8]> {
+        Some(<Self as SliceInput>::slice_from(self, offset..))
+    }
+
+    #[inline(always)]
+    fn skip_bytes(offset: Self::Offset, bytes: usize) -> Self::Offset {
+        offset + bytes
+    }
+}
+
 impl<'a, T: 'a, const N: usize> SliceInput<'a> for &'a [T; N] {
     type Slice = &'a [T];
 
@@ -452,6 +665,14 @@ where
     fn prev(offs: Self::Offset) -> Self::Offset {
         I::prev(offs)
     }
+
+    fn invalid_tokens(&self) -> Vec<(Self::Offset, Self::Token, Self::Span)> {
+        self.input
+            .invalid_tokens()
+            .into_iter()
+            .map(|(at, (tok, span), _)| (at, tok, span))
+            .collect()
+    }
 }
 
 impl<'a, T, S, I> ExactSizeInput<'a> for SpannedInput<T, S, I>
@@ -484,6 +705,20 @@ where
     }
 }
 
+impl<'a, C, S, I> TextInput<'a, C> for SpannedInput<C, S, I>
+where
+    I: ValueInput<'a, Token = (C, S)>,
+    C: Char,
+    S: Span + Clone + 'a,
+{
+    type Text = C::Owned;
+
+    #[inline(always)]
+    unsafe fn text(&self, range: Range<Self::Offset>) -> Self::Text {
+        collect_text(self, range)
+    }
+}
+
 impl<'a, T, S, I> BorrowInput<'a> for SpannedInput<T, S, I>
 where
     I: Input<'a> + BorrowInput<'a, Token = (T, S)>,
@@ -554,6 +789,14 @@ where
     fn prev(offs: Self::Offset) -> Self::Offset {
         I::prev(offs)
     }
+
+    fn invalid_tokens(&self) -> Vec<(Self::Offset, Self::Token, Self::Span)> {
+        self.input
+            .invalid_tokens()
+            .into_iter()
+            .map(|(at, tok, span)| (at, tok, (self.context.clone(), span)))
+            .collect()
+    }
 }
 
 impl<'a, Ctx: Clone + 'a, I: Input<'a>> ExactSizeInput<'a> for WithContext<Ctx, I>
@@ -604,6 +847,31 @@ where
     }
 }
 
+impl<'a, Ctx, C, I> TextInput<'a, C> for WithContext<Ctx, I>
+where
+    I: TextInput<'a, C>,
+    I::Span: Span<Context = ()>,
+    Ctx: Clone + 'a,
+    C: Char,
+{
+    type Text = I::Text;
+
+    #[inline(always)]
+    unsafe fn text(&self, range: Range<Self::Offset>) -> Self::Text {
+        self.input.text(range)
+    }
+
+    #[inline(always)]
+    fn trailing_str(&self, offset: Self::Offset) -> Option<&'a C::Str> {
+        self.input.trailing_str(offset)
+    }
+
+    #[inline(always)]
+    fn skip_bytes(offset: Self::Offset, bytes: usize) -> Self::Offset {
+        I::skip_bytes(offset, bytes)
+    }
+}
+
 impl<'a, Ctx, C, I> StrInput<'a, C> for WithContext<Ctx, I>
 where
     I: StrInput<'a, C>,
@@ -613,12 +881,286 @@ where
 {
 }
 
+/// An input wrapper that translates the spans of the wrapped input into the coordinates of another input. See
+/// [`Input::rebase`].
+#[derive(Copy, Clone)]
+pub struct Rebased<S, I> {
+    input: I,
+    base: S,
+}
+
+impl<S: Span<Offset = usize>, I> Rebased<S, I> {
+    fn rebase(&self, span: impl Span<Offset = usize>) -> S {
+        let start = self.base.start();
+        S::new(
+            self.base.context(),
+            start + span.start()..start + span.end(),
+        )
+    }
+}
+
+impl<S, I> Sealed for Rebased<S, I> {}
+impl<'a, S, I> Input<'a> for Rebased<S, I>
+where
+    S: Span<Offset = usize> + Clone + 'a,
+    I: Input<'a>,
+    I::Span: Span<Offset = usize, Context = ()>,
+{
+    type Offset = I::Offset;
+    type Token = I::Token;
+    type Span = S;
+
+    #[inline(always)]
+    fn start(&self) -> Self::Offset {
+        self.input.start()
+    }
+
+    type TokenMaybe = I::TokenMaybe;
+
+    #[inline(always)]
+    unsafe fn next_maybe(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::TokenMaybe>) {
+        self.input.next_maybe(offset)
+    }
+
+    #[inline(always)]
+    unsafe fn span(&self, range: Range<Self::Offset>) -> Self::Span {
+        self.rebase(self.input.span(range))
+    }
+
+    #[inline(always)]
+    fn prev(offs: Self::Offset) -> Self::Offset {
+        I::prev(offs)
+    }
+
+    fn invalid_tokens(&self) -> Vec<(Self::Offset, Self::Token, Self::Span)> {
+        self.input
+            .invalid_tokens()
+            .into_iter()
+            .map(|(at, tok, span)| (at, tok, self.rebase(span)))
+            .collect()
+    }
+}
+
+impl<'a, S, I> ExactSizeInput<'a> for Rebased<S, I>
+where
+    S: Span<Offset = usize> + Clone + 'a,
+    I: ExactSizeInput<'a>,
+    I::Span: Span<Offset = usize, Context = ()>,
+{
+    #[inline(always)]
+    unsafe fn span_from(&self, range: RangeFrom<Self::Offset>) -> Self::Span {
+        self.rebase(self.input.span_from(range))
+    }
+}
+
+impl<'a, S, I> ValueInput<'a> for Rebased<S, I>
+where
+    S: Span<Offset = usize> + Clone + 'a,
+    I: ValueInput<'a>,
+    I::Span: Span<Offset = usize, Context = ()>,
+{
+    #[inline(always)]
+    unsafe fn next(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::Token>) {
+        self.input.next(offset)
+    }
+}
+
+impl<'a, S, I> BorrowInput<'a> for Rebased<S, I>
+where
+    S: Span<Offset = usize> + Clone + 'a,
+    I: BorrowInput<'a>,
+    I::Span: Span<Offset = usize, Context = ()>,
+{
+    #[inline(always)]
+    unsafe fn next_ref(&self, offset: Self::Offset) -> (Self::Offset, Option<&'a Self::Token>) {
+        self.input.next_ref(offset)
+    }
+}
+
+impl<'a, S, I> SliceInput<'a> for Rebased<S, I>
+where
+    S: Span<Offset = usize> + Clone + 'a,
+    I: SliceInput<'a>,
+    I::Span: Span<Offset = usize, Context = ()>,
+{
+    type Slice = I::Slice;
+
+    #[inline(always)]
+    fn slice(&self, range: Range<Self::Offset>) -> Self::Slice {
+        <I as SliceInput>::slice(&self.input, range)
+    }
+
+    #[inline(always)]
+    fn slice_from(&self, from: RangeFrom<Self::Offset>) -> Self::Slice {
+        <I as SliceInput>::slice_from(&self.input, from)
+    }
+}
+
+impl<'a, S, C, I> TextInput<'a, C> for Rebased<S, I>
+where
+    S: Span<Offset = usize> + Clone + 'a,
+    I: TextInput<'a, C>,
+    I::Span: Span<Offset = usize, Context = ()>,
+    C: Char,
+{
+    type Text = I::Text;
+
+    #[inline(always)]
+    unsafe fn text(&self, range: Range<Self::Offset>) -> Self::Text {
+        self.input.text(range)
+    }
+
+    #[inline(always)]
+    fn trailing_str(&self, offset: Self::Offset) -> Option<&'a C::Str> {
+        self.input.trailing_str(offset)
+    }
+
+    #[inline(always)]
+    fn skip_bytes(offset: Self::Offset, bytes: usize) -> Self::Offset {
+        I::skip_bytes(offset, bytes)
+    }
+}
+
+impl<'a, S, C, I> StrInput<'a, C> for Rebased<S, I>
+where
+    S: Span<Offset = usize> + Clone + 'a,
+    I: StrInput<'a, C>,
+    I::Span: Span<Offset = usize, Context = ()>,
+    C: Char,
+{
+}
+
+/// An input wrapper that skips over tokens matching a predicate, such as whitespace or comments. See
+/// [`Input::skip_tokens`].
+#[derive(Copy, Clone)]
+pub struct SkipTokens<F, I> {
+    input: I,
+    skip: F,
+}
+
+impl<'a, F, I> SkipTokens<F, I>
+where
+    I: Input<'a>,
+    F: Fn(&I::Token) -> bool,
+{
+    // Get the offset of the next token that should not be skipped
+    #[inline]
+    unsafe fn skip(&self, mut offset: I::Offset) -> I::Offset {
+        loop {
+            match self.input.next_maybe(offset) {
+                (next, Some(tok)) if (self.skip)(tok.borrow()) => offset = next,
+                _ => break offset,
+            }
+        }
+    }
+
+    // Skip tokens at the start of the range, so that spans and slices don't begin with skipped tokens
+    #[inline]
+    unsafe fn skip_range(&self, range: Range<I::Offset>) -> Range<I::Offset> {
+        let start = self.skip(range.start);
+        start..range.end.max(start)
+    }
+}
+
+impl<F, I> Sealed for SkipTokens<F, I> {}
+impl<'a, F, I> Input<'a> for SkipTokens<F, I>
+where
+    I: Input<'a>,
+    F: Fn(&I::Token) -> bool + 'a,
+{
+    type Offset = I::Offset;
+    type Token = I::Token;
+    type Span = I::Span;
+
+    #[inline(always)]
+    fn start(&self) -> Self::Offset {
+        self.input.start()
+    }
+
+    type TokenMaybe = I::TokenMaybe;
+
+    #[inline(always)]
+    unsafe fn next_maybe(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::TokenMaybe>) {
+        self.input.next_maybe(self.skip(offset))
+    }
+
+    #[inline(always)]
+    unsafe fn span(&self, range: Range<Self::Offset>) -> Self::Span {
+        self.input.span(self.skip_range(range))
+    }
+
+    #[inline(always)]
+    fn prev(offs: Self::Offset) -> Self::Offset {
+        I::prev(offs)
+    }
+
+    fn invalid_tokens(&self) -> Vec<(Self::Offset, Self::Token, Self::Span)> {
+        self.input.invalid_tokens()
+    }
+}
+
+impl<'a, F, I> ExactSizeInput<'a> for SkipTokens<F, I>
+where
+    I: ExactSizeInput<'a>,
+    F: Fn(&I::Token) -> bool + 'a,
+{
+    #[inline(always)]
+    unsafe fn span_from(&self, range: RangeFrom<Self::Offset>) -> Self::Span {
+        self.input.span_from(self.skip(range.start)..)
+    }
+}
+
+impl<'a, F, I> ValueInput<'a> for SkipTokens<F, I>
+where
+    I: ValueInput<'a>,
+    F: Fn(&I::Token) -> bool + 'a,
+{
+    #[inline(always)]
+    unsafe fn next(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::Token>) {
+        self.input.next(self.skip(offset))
+    }
+}
+
+impl<'a, F, I> BorrowInput<'a> for SkipTokens<F, I>
+where
+    I: BorrowInput<'a>,
+    F: Fn(&I::Token) -> bool + 'a,
+{
+    #[inline(always)]
+    unsafe fn next_ref(&self, offset: Self::Offset) -> (Self::Offset, Option<&'a Self::Token>) {
+        self.input.next_ref(self.skip(offset))
+    }
+}
+
+impl<'a, F, I> SliceInput<'a> for SkipTokens<F, I>
+where
+    I: SliceInput<'a>,
+    F: Fn(&I::Token) -> bool + 'a,
+{
+    type Slice = I::Slice;
+
+    #[inline(always)]
+    fn slice(&self, range: Range<Self::Offset>) -> Self::Slice {
+        // SAFETY: slice offsets are generated by the parser, which only produces valid offsets
+        let range = unsafe { self.skip_range(range) };
+        <I as SliceInput>::slice(&self.input, range)
+    }
+
+    #[inline(always)]
+    fn slice_from(&self, from: RangeFrom<Self::Offset>) -> Self::Slice {
+        // SAFETY: as above
+        let start = unsafe { self.skip(from.start) };
+        <I as SliceInput>::slice_from(&self.input, start..)
+    }
+}
+
 /// Represents a location in an input that can be rewound to.
 ///
 /// Markers can be created with [`InputRef::save`] and rewound to with [`InputRef::rewind`].
 pub struct Marker<'a, 'parse, I: Input<'a>> {
     pub(crate) offset: I::Offset,
     pub(crate) err_count: usize,
+    pub(crate) event_count: usize,
     phantom: PhantomData<fn(&'parse ()) -> &'parse ()>, // Invariance
 }
 
@@ -662,9 +1204,28 @@ impl<'a, 'parse, I: Input<'a>> PartialEq for Offset<'a, 'parse, I> {
     }
 }
 
+// Something other than an error that happened during parsing and must be undone when backtracking
+pub(crate) enum Event<T, E> {
+    Warning(Located<T, E>),
+    // The range of input skipped (or, if empty, patched over) by error recovery
+    Recovered(Range<T>),
+    // Parsing was halted at the given position because one of the limits in `ParseOptions` was reached
+    Halted(T),
+}
+
 pub(crate) struct Errors<T, E> {
     pub(crate) alt: Option<Located<T, E>>,
     pub(crate) secondary: Vec<Located<T, E>>,
+    // Events are rare, so are kept together so that markers only need to track a single count for them
+    pub(crate) events: Vec<Event<T, E>>,
+    pub(crate) options: ParseOptions,
+    pub(crate) recoveries: usize,
+    // Whether `events` contains a halt
+    pub(crate) halted: bool,
+    // The furthest position at which parsing was halted, kept after backtracking so that it can still be reported
+    pub(crate) furthest_halt: Option<T>,
+    #[cfg(feature = "profile")]
+    pub(crate) profiler: Option<crate::profile::Profiler>,
 }
 
 impl<T, E> Errors<T, E> {
@@ -673,6 +1234,66 @@ impl<T, E> Errors<T, E> {
     pub(crate) fn secondary_errors_since(&mut self, err_count: usize) -> &mut [Located<T, E>] {
         self.secondary.get_mut(err_count..).unwrap_or(&mut [])
     }
+
+    /// Returns whether a recovery strategy may attempt recovery at the given position, counting the attempt if so.
+    ///
+    /// Once any of the limits in [`ParseOptions`] has been reached, this (and all future calls to it) return `false`,
+    /// causing the parse to halt at the next error.
+    #[inline]
+    pub(crate) fn begin_recovery(&mut self, at: T) -> bool
+    where
+        T: Copy + Ord,
+    {
+        if self.halted {
+            return false;
+        }
+        let too_many_errors =
+            matches!(self.options.max_errors, Some(max) if self.secondary.len() >= max);
+        let too_many_recoveries =
+            matches!(self.options.max_recoveries, Some(max) if self.recoveries >= max);
+        if too_many_errors || too_many_recoveries {
+            self.events.push(Event::Halted(at));
+            self.halted = true;
+            self.furthest_halt = self.furthest_halt.max(Some(at));
+            false
+        } else {
+            self.recoveries += 1;
+            true
+        }
+    }
+
+    /// Returns the range of input skipped by the error recoveries (if any) that have taken place since the given
+    /// marker was created.
+    #[inline]
+    pub(crate) fn recovered_since(&self, event_count: usize) -> Option<Range<T>>
+    where
+        T: Copy + Ord,
+    {
+        let recovered = self
+            .events
+            .get(event_count..)
+            .unwrap_or(&[])
+            .iter()
+            .filter_map(|event| match event {
+                Event::Recovered(range) => Some(range),
+                _ => None,
+            });
+        let start = recovered.clone().map(|r| r.start).min()?;
+        let end = recovered.map(|r| r.end).max()?;
+        Some(start..end)
+    }
+
+    /// Undo the events that have happened since the given marker was created.
+    #[cold]
+    pub(crate) fn rewind_events(&mut self, event_count: usize) {
+        self.events.truncate(event_count);
+        if self.halted {
+            self.halted = self
+                .events
+                .iter()
+                .any(|event| matches!(event, Event::Halted(_)));
+        }
+    }
 }
 
 impl<T, E> Default for Errors<T, E> {
@@ -680,6 +1301,13 @@ impl<T, E> Default for Errors<T, E> {
         Self {
             alt: None,
             secondary: Vec::new(),
+            events: Vec::new(),
+            options: ParseOptions::default(),
+            recoveries: 0,
+            halted: false,
+            furthest_halt: None,
+            #[cfg(feature = "profile")]
+            profiler: None,
         }
     }
 }
@@ -707,8 +1335,8 @@ where
         E::Context: Default,
     {
         InputOwn {
+            errors: Self::initial_errors(&input),
             input,
-            errors: Errors::default(),
             state: MaybeMut::Val(E::State::default()),
             ctx: E::Context::default(),
             #[cfg(feature = "memoization")]
@@ -721,8 +1349,8 @@ where
         E::Context: Default,
     {
         InputOwn {
+            errors: Self::initial_errors(&input),
             input,
-            errors: Errors::default(),
             state: MaybeMut::Ref(state),
             ctx: E::Context::default(),
             #[cfg(feature = "memoization")]
@@ -730,6 +1358,20 @@ where
         }
     }
 
+    // Report any tokens that the input skipped as secondary errors
+    fn initial_errors(input: &I) -> Errors<I::Offset, E::Error> {
+        Errors {
+            secondary: input
+                .invalid_tokens()
+                .into_iter()
+                .map(|(at, tok, span)| {
+                    Located::at(at, E::Error::expected_found(None, Some(tok.into()), span))
+                })
+                .collect(),
+            ..Errors::default()
+        }
+    }
+
     pub(crate) fn as_ref_start<'parse>(&'parse mut self) -> InputRef<'a, 'parse, I, E> {
         InputRef {
             offset: self.input.start(),
@@ -758,12 +1400,22 @@ where
         }
     }
 
-    pub(crate) fn into_errs(self) -> Vec<E::Error> {
-        self.errors
-            .secondary
-            .into_iter()
-            .map(|err| err.err)
-            .collect()
+    pub(crate) fn into_errs_and_warnings(self) -> (Vec<E::Error>, Vec<E::Error>) {
+        (
+            self.errors
+                .secondary
+                .into_iter()
+                .map(|err| err.err)
+                .collect(),
+            self.errors
+                .events
+                .into_iter()
+                .filter_map(|event| match event {
+                    Event::Warning(warning) => Some(warning.err),
+                    _ => None,
+                })
+                .collect(),
+        )
     }
 }
 
@@ -847,6 +1499,7 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
         Marker {
             offset: self.offset,
             err_count: self.errors.secondary.len(),
+            event_count: self.errors.events.len(),
             phantom: PhantomData,
         }
     }
@@ -857,6 +1510,9 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
     #[inline(always)]
     pub fn rewind(&mut self, marker: Marker<'a, 'parse, I>) {
         self.errors.secondary.truncate(marker.err_count);
+        if self.errors.events.len() > marker.event_count {
+            self.errors.rewind_events(marker.event_count);
+        }
         self.offset = marker.offset;
     }
 
@@ -1102,14 +1758,34 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
         unsafe { self.input.span(before.offset..self.offset) }
     }
 
+    #[inline(always)]
+    pub(crate) fn text_inner<C>(&self, range: Range<I::Offset>) -> I::Text
+    where
+        C: Char,
+        I: TextInput<'a, C>,
+    {
+        // SAFETY: offsets are only ever generated by `Input::start` or `ValueInput::next`
+        unsafe { self.input.text(range) }
+    }
+
+    #[cfg(feature = "regex")]
+    #[inline(always)]
+    pub(crate) fn trailing_str<C>(&self) -> Option<&'a C::Str>
+    where
+        C: Char,
+        I: TextInput<'a, C>,
+    {
+        self.input.trailing_str(self.offset)
+    }
+
     #[cfg(feature = "regex")]
     #[inline(always)]
     pub(crate) fn skip_bytes<C>(&mut self, skip: usize)
     where
         C: Char,
-        I: StrInput<'a, C>,
+        I: TextInput<'a, C>,
     {
-        self.offset += skip;
+        self.offset = I::skip_bytes(self.offset, skip);
     }
 
     #[inline]
@@ -1117,6 +1793,31 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
         self.errors.secondary.push(Located::at(pos, error));
     }
 
+    /// Record that the input from `start` to the current offset was skipped (or, if empty, patched over) by error
+    /// recovery.
+    #[inline]
+    pub(crate) fn mark_recovered(&mut self, start: I::Offset) {
+        self.errors
+            .events
+            .push(Event::Recovered(start..self.offset));
+    }
+
+    #[inline]
+    pub(crate) fn emit_warning(&mut self, pos: I::Offset, warning: E::Error) {
+        self.errors
+            .events
+            .push(Event::Warning(Located::at(pos, warning)));
+    }
+
+    /// Emit a non-fatal warning at the current input position.
+    ///
+    /// Unlike errors, warnings do not cause [`ParseResult::has_errors`] to return `true`. As with secondary errors,
+    /// warnings emitted by a parser that is later backtracked out of (see [`InputRef::rewind`]) are discarded.
+    #[inline]
+    pub fn warn(&mut self, warning: E::Error) {
+        self.emit_warning(self.offset, warning);
+    }
+
     #[inline]
     pub(crate) fn add_alt<Exp: IntoIterator<Item = Option<MaybeRef<'a, I::Token>>>>(
         &mut self,
@@ -1152,11 +1853,26 @@ impl<'a, 'parse, I: Input<'a>, E: ParserExtra<'a, I>> InputRef<'a, 'parse, I, E>
             None => Located::at(at, err),
         });
     }
+
+    // Restore an alternative error that was taken before trying some patterns, merging it with any alternative errors
+    // that were generated in the meantime as if it had never been taken
+    #[inline]
+    pub(crate) fn restore_alt(&mut self, alt: Option<Located<I::Offset, E::Error>>) {
+        self.errors.alt = match (alt, self.errors.alt.take()) {
+            (Some(old), Some(new)) => Some(match old.pos.into().cmp(&new.pos.into()) {
+                Ordering::Equal => Located::at(old.pos, old.err.merge(new.err)),
+                Ordering::Greater => old,
+                Ordering::Less => new,
+            }),
+            (old, new) => old.or(new),
+        };
+    }
 }
 
-/// Struct used in [`Parser::validate`] to collect user-emitted errors
+/// Struct used in [`Parser::validate`] to collect user-emitted errors and warnings
 pub struct Emitter<E> {
     emitted: Vec<E>,
+    warnings: Vec<E>,
 }
 
 impl<E> Emitter<E> {
@@ -1164,12 +1880,13 @@ impl<E> Emitter<E> {
     pub(crate) fn new() -> Emitter<E> {
         Emitter {
             emitted: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
     #[inline]
-    pub(crate) fn errors(self) -> Vec<E> {
-        self.emitted
+    pub(crate) fn into_parts(self) -> (Vec<E>, Vec<E>) {
+        (self.emitted, self.warnings)
     }
 
     /// Emit a non-fatal error
@@ -1177,4 +1894,13 @@ impl<E> Emitter<E> {
     pub fn emit(&mut self, err: E) {
         self.emitted.push(err)
     }
+
+    /// Emit a warning.
+    ///
+    /// Warnings are reported separately from errors (see [`ParseResult::warnings`]) and do not cause
+    /// [`ParseResult::has_errors`] to return `true`.
+    #[inline]
+    pub fn warn(&mut self, warning: E) {
+        self.warnings.push(warning)
+    }
 }
@@ -0,0 +1,536 @@
+//! Parsers and utilities for matching tokens by their kind.
+//!
+//! Token-level grammars (i.e: those that parse the output of a lexer) usually care about which variant a token is,
+//! not about its payload: an identifier is expected, not `Token::Ident("foo")` in particular. Implementing
+//! [`TokenKind`] for a token type allows it to be matched by kind with [`kind()`] and [`one_of_kinds`].
+//!
+//! Expected kinds are tracked in a [`KindSet`], a bitset that is cheap to create, copy and merge. Errors that support
+//! them (see [`Error::expected_kinds`]) report expected kinds by name rather than by cloning tokens. [`KindError`] is
+//! an error type that tracks nothing else.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, error::RichPattern, kind::{kind, one_of_kinds, TokenKind}};
+//! #[derive(Clone, Debug, PartialEq)]
+//! enum Token {
+//!     Ident(String),
+//!     Num(u64),
+//!     Plus,
+//! }
+//!
+//! #[derive(Copy, Clone, PartialEq)]
+//! enum Kind {
+//!     Ident,
+//!     Num,
+//!     Plus,
+//! }
+//!
+//! impl TokenKind for Token {
+//!     type Kind = Kind;
+//!
+//!     fn kind(&self) -> Kind {
+//!         match self {
+//!             Token::Ident(_) => Kind::Ident,
+//!             Token::Num(_) => Kind::Num,
+//!             Token::Plus => Kind::Plus,
+//!         }
+//!     }
+//!
+//!     fn kind_index(kind: Kind) -> u8 {
+//!         kind as u8
+//!     }
+//!
+//!     fn kind_from_index(index: u8) -> Kind {
+//!         [Kind::Ident, Kind::Num, Kind::Plus][index as usize]
+//!     }
+//!
+//!     fn kind_name(kind: Kind) -> &'static str {
+//!         match kind {
+//!             Kind::Ident => "identifier",
+//!             Kind::Num => "number",
+//!             Kind::Plus => "'+'",
+//!         }
+//!     }
+//! }
+//!
+//! let atom = one_of_kinds::<&[Token], extra::Err<Rich<Token>>>([Kind::Ident, Kind::Num]);
+//! let sum = atom
+//!     .clone()
+//!     .then(kind(Kind::Plus).ignore_then(atom).repeated().collect::<Vec<_>>());
+//!
+//! let tokens = [Token::Num(1), Token::Plus, Token::Ident("x".into())];
+//! assert!(sum.parse(&tokens).has_output());
+//!
+//! let tokens = [Token::Plus];
+//! let errs = sum.parse(&tokens).into_errors();
+//! assert_eq!(
+//!     errs[0].expected().collect::<Vec<_>>(),
+//!     [&RichPattern::Kind("identifier"), &RichPattern::Kind("number")],
+//! );
+//! ```
+
+use super::*;
+
+/// A trait implemented by token types whose values can be grouped into kinds, usually one per variant.
+///
+/// Each kind is identified by a small integer index (see [`TokenKind::kind_index`]), allowing sets of kinds to be
+/// stored as a bitset (see [`KindSet`]).
+pub trait TokenKind {
+    /// The type of the kinds of this token, usually a fieldless enum mirroring the variants of the token type.
+    type Kind: Copy + PartialEq;
+
+    /// Get the kind of this token.
+    fn kind(&self) -> Self::Kind;
+
+    /// Get the index that identifies the given kind.
+    ///
+    /// Indices must be unique to each kind and less than [`KindSet::CAPACITY`].
+    fn kind_index(kind: Self::Kind) -> u8;
+
+    /// Get the kind identified by the given index (the inverse of [`TokenKind::kind_index`]).
+    fn kind_from_index(index: u8) -> Self::Kind;
+
+    /// Get a human-readable name for the given kind, used when displaying errors.
+    fn kind_name(kind: Self::Kind) -> &'static str;
+}
+
+/// A set of token kinds, stored as a bitset. See [`TokenKind`].
+pub struct KindSet<T> {
+    bits: u128,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> Copy for KindSet<T> {}
+impl<T> Clone for KindSet<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> PartialEq for KindSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+impl<T> Eq for KindSet<T> {}
+
+impl<T> Hash for KindSet<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<T> Default for KindSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> KindSet<T> {
+    /// The number of kinds that a set can hold. All kind indices must be less than this.
+    pub const CAPACITY: u8 = 128;
+
+    /// Create an empty set.
+    pub const fn new() -> Self {
+        Self {
+            bits: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns true if the set contains no kinds.
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Get the number of kinds in the set.
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Get the set of kinds contained in either this set or the other.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            bits: self.bits | other.bits,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: TokenKind> KindSet<T> {
+    /// Add a kind to the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index of the kind is not less than [`KindSet::CAPACITY`].
+    pub fn insert(&mut self, kind: T::Kind) {
+        self.bits |= Self::bit(kind);
+    }
+
+    /// Returns true if the set contains the given kind.
+    pub fn contains(&self, kind: T::Kind) -> bool {
+        self.bits & Self::bit(kind) != 0
+    }
+
+    /// Iterate over the kinds in the set, in order of increasing index.
+    pub fn iter(&self) -> impl Iterator<Item = T::Kind> {
+        let bits = self.bits;
+        (0..Self::CAPACITY)
+            .filter(move |idx| bits & (1 << idx) != 0)
+            .map(T::kind_from_index)
+    }
+
+    fn bit(kind: T::Kind) -> u128 {
+        let idx = T::kind_index(kind);
+        assert!(
+            idx < Self::CAPACITY,
+            "token kind index {} is out of range, indices must be less than {}",
+            idx,
+            Self::CAPACITY,
+        );
+        1 << idx
+    }
+}
+
+impl<T: TokenKind> FromIterator<T::Kind> for KindSet<T> {
+    fn from_iter<I: IntoIterator<Item = T::Kind>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for kind in iter {
+            set.insert(kind);
+        }
+        set
+    }
+}
+
+impl<T: TokenKind> fmt::Debug for KindSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set()
+            .entries(self.iter().map(T::kind_name))
+            .finish()
+    }
+}
+
+/// Displays the names of the kinds in the set as a list, such as `identifier, number, or string`.
+impl<T: TokenKind> fmt::Display for KindSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let len = self.len();
+        for (i, kind) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            if len > 1 && i == len - 1 {
+                write!(f, "or ")?;
+            }
+            write!(f, "{}", T::kind_name(kind))?;
+        }
+        Ok(())
+    }
+}
+
+/// See [`kind()`] and [`one_of_kinds`].
+pub struct OneOfKinds<T, I, E> {
+    kinds: KindSet<T>,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(E, I)>,
+}
+
+impl<T, I, E> Copy for OneOfKinds<T, I, E> {}
+impl<T, I, E> Clone for OneOfKinds<T, I, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: TokenKind, I, E> fmt::Debug for OneOfKinds<T, I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("OneOfKinds").field(&self.kinds).finish()
+    }
+}
+
+/// A parser that accepts a single token of the given kind, ignoring its payload.
+///
+/// The output type of this parser is `I::Token`, the token that was accepted.
+///
+/// See the [module-level documentation](self) for an example.
+pub fn kind<'a, I, E>(kind: <I::Token as TokenKind>::Kind) -> OneOfKinds<I::Token, I, E>
+where
+    I: ValueInput<'a>,
+    I::Token: TokenKind,
+    E: ParserExtra<'a, I>,
+{
+    one_of_kinds(Some(kind))
+}
+
+/// A parser that accepts a single token of any of the given kinds, ignoring its payload.
+///
+/// The output type of this parser is `I::Token`, the token that was accepted.
+///
+/// See the [module-level documentation](self) for an example.
+pub fn one_of_kinds<'a, I, E>(
+    kinds: impl IntoIterator<Item = <I::Token as TokenKind>::Kind>,
+) -> OneOfKinds<I::Token, I, E>
+where
+    I: ValueInput<'a>,
+    I::Token: TokenKind,
+    E: ParserExtra<'a, I>,
+{
+    OneOfKinds {
+        kinds: kinds.into_iter().collect(),
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+impl<T: TokenKind, I, E> OneOfKinds<T, I, E> {
+    /// Also accept tokens of the given kind.
+    pub fn with_kind(mut self, kind: T::Kind) -> Self {
+        self.kinds.insert(kind);
+        self
+    }
+
+    /// Get the set of kinds accepted by this parser.
+    pub fn kinds(&self) -> KindSet<T> {
+        self.kinds
+    }
+}
+
+impl<'a, I, E> ParserSealed<'a, I, I::Token, E> for OneOfKinds<I::Token, I, E>
+where
+    I: ValueInput<'a>,
+    I::Token: TokenKind,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, I::Token> {
+        let before = inp.offset();
+        match inp.next_inner() {
+            (_, Some(tok)) if self.kinds.contains(tok.kind()) => Ok(M::bind(|| tok)),
+            (at, found) => {
+                let err = E::Error::expected_kinds(
+                    self.kinds,
+                    found.map(MaybeRef::Val),
+                    inp.span_since(before),
+                );
+                inp.add_alt_err(at, err);
+                Err(())
+            }
+        }
+    }
+
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::token(None)
+    }
+
+    go_extra!(I::Token);
+}
+
+/// An error type that tracks only the error span, the kind of the token found, and the kinds of token that were
+/// expected (as a [`KindSet`]).
+///
+/// Expected tokens reported by other parsers (such as [`just`]) are converted to their kinds. This makes it a cheap,
+/// yet informative, error type for token-level grammars.
+pub struct KindError<T: TokenKind, S = SimpleSpan<usize>> {
+    span: S,
+    expected: KindSet<T>,
+    found: Option<T::Kind>,
+}
+
+impl<T: TokenKind, S> KindError<T, S> {
+    /// Get the span of the error.
+    pub fn span(&self) -> &S {
+        &self.span
+    }
+
+    /// Get the kinds of token that were expected.
+    pub fn expected(&self) -> KindSet<T> {
+        self.expected
+    }
+
+    /// Get the kind of the token that was found, or `None` if the end of input was found.
+    pub fn found(&self) -> Option<T::Kind> {
+        self.found
+    }
+}
+
+impl<T: TokenKind, S: Clone> Clone for KindError<T, S> {
+    fn clone(&self) -> Self {
+        Self {
+            span: self.span.clone(),
+            expected: self.expected,
+            found: self.found,
+        }
+    }
+}
+
+impl<T: TokenKind, S: PartialEq> PartialEq for KindError<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.span == other.span && self.expected == other.expected && self.found == other.found
+    }
+}
+
+impl<'a, I: Input<'a>> Error<'a, I> for KindError<I::Token, I::Span>
+where
+    I::Token: TokenKind,
+{
+    #[inline]
+    fn expected_found<E: IntoIterator<Item = Option<MaybeRef<'a, I::Token>>>>(
+        expected: E,
+        found: Option<MaybeRef<'a, I::Token>>,
+        span: I::Span,
+    ) -> Self {
+        Self {
+            span,
+            expected: expected
+                .into_iter()
+                .flatten()
+                .map(|tok| tok.kind())
+                .collect(),
+            found: found.map(|tok| tok.kind()),
+        }
+    }
+
+    #[inline]
+    fn expected_kinds(
+        expected: KindSet<I::Token>,
+        found: Option<MaybeRef<'a, I::Token>>,
+        span: I::Span,
+    ) -> Self {
+        Self {
+            span,
+            expected,
+            found: found.map(|tok| tok.kind()),
+        }
+    }
+
+    #[inline]
+    fn merge(mut self, other: Self) -> Self {
+        self.expected = self.expected.union(other.expected);
+        self
+    }
+}
+
+impl<T: TokenKind, S: fmt::Debug> fmt::Debug for KindError<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {:?}", self, self.span)
+    }
+}
+
+impl<T: TokenKind, S> fmt::Display for KindError<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.found {
+            Some(found) => write!(f, "found {}", T::kind_name(found))?,
+            None => write!(f, "found end of input")?,
+        }
+        if self.expected.is_empty() {
+            write!(f, " expected something else")
+        } else {
+            write!(f, " expected {}", self.expected)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Token {
+        Ident(&'static str),
+        Num(u64),
+        Plus,
+        Star,
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    enum Kind {
+        Ident,
+        Num,
+        Plus,
+        Star,
+    }
+
+    impl TokenKind for Token {
+        type Kind = Kind;
+
+        fn kind(&self) -> Kind {
+            match self {
+                Token::Ident(_) => Kind::Ident,
+                Token::Num(_) => Kind::Num,
+                Token::Plus => Kind::Plus,
+                Token::Star => Kind::Star,
+            }
+        }
+
+        fn kind_index(kind: Kind) -> u8 {
+            kind as u8
+        }
+
+        fn kind_from_index(index: u8) -> Kind {
+            [Kind::Ident, Kind::Num, Kind::Plus, Kind::Star][index as usize]
+        }
+
+        fn kind_name(kind: Kind) -> &'static str {
+            match kind {
+                Kind::Ident => "identifier",
+                Kind::Num => "number",
+                Kind::Plus => "'+'",
+                Kind::Star => "'*'",
+            }
+        }
+    }
+
+    #[test]
+    fn kind_errors() {
+        fn parser<'a>() -> impl Parser<'a, &'a [Token], Vec<Token>, extra::Err<KindError<Token>>> {
+            let atom = one_of_kinds([Kind::Ident, Kind::Num]);
+            atom.then(kind(Kind::Plus).or(just(Token::Star)).then(atom))
+                .map(|(a, (op, b))| vec![a, op, b])
+        }
+
+        assert_eq!(
+            parser()
+                .parse(&[Token::Ident("x"), Token::Star, Token::Num(2)])
+                .into_result(),
+            Ok(vec![Token::Ident("x"), Token::Star, Token::Num(2)]),
+        );
+
+        let errs = parser()
+            .parse(&[Token::Num(1), Token::Num(2)])
+            .into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(1..2));
+        assert_eq!(errs[0].found(), Some(Kind::Num));
+        // Kinds from `kind` and tokens from `just` are merged into the same set
+        assert_eq!(
+            errs[0].expected().iter().collect::<Vec<_>>(),
+            [Kind::Plus, Kind::Star]
+        );
+        assert_eq!(errs[0].to_string(), "found number expected '+', or '*'");
+
+        let errs = parser().parse(&[Token::Num(1), Token::Plus]).into_errors();
+        assert_eq!(
+            errs[0].to_string(),
+            "found end of input expected identifier, or number"
+        );
+    }
+
+    #[test]
+    fn kind_set() {
+        let mut set = KindSet::<Token>::new();
+        assert!(set.is_empty());
+        set.insert(Kind::Star);
+        set.insert(Kind::Ident);
+        set.insert(Kind::Star);
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(Kind::Ident) && !set.contains(Kind::Num));
+        assert_eq!(
+            set.union([Kind::Num].into_iter().collect()).to_string(),
+            "identifier, number, or '*'"
+        );
+        assert_eq!(format!("{:?}", set), r#"{"identifier", "'*'"}"#);
+    }
+}
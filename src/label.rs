@@ -26,6 +26,15 @@ pub struct Labelled<A, L> {
     pub(crate) is_context: bool,
 }
 
+impl<A: fmt::Debug, L: fmt::Debug> fmt::Debug for Labelled<A, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Labelled")
+            .field(&self.parser)
+            .field(&self.label)
+            .finish()
+    }
+}
+
 impl<A, L> Labelled<A, L> {
     /// Specify that the label should be used as context when reporting errors.
     ///
@@ -52,33 +61,117 @@ where
         let before = inp.save();
         let res = self.parser.go::<M>(inp);
 
-        // TODO: Label secondary errors too?
         let new_alt = inp.errors.alt.take();
         inp.errors.alt = old_alt;
 
-        if let Some(mut new_alt) = new_alt {
-            let before_next = before.offset.into() + 1;
-            if new_alt.pos.into() == before_next {
-                new_alt.err.label_with(self.label.clone());
-            } else if self.is_context && new_alt.pos.into() > before_next {
+        // Errors that occur at the very start of the pattern are labelled, while those that occur within it (if the
+        // label is a context) are annotated with the context instead. Secondary errors are treated just like the alt
+        // error so that recovered errors are reported consistently.
+        let before_offset = before.offset;
+        let before_next = before_offset.into() + 1;
+        let annotate = |inp: &InputRef<'a, '_, I, E>, pos: I::Offset, err: &mut E::Error| {
+            if pos.into() == before_next {
+                err.label_with(self.label.clone());
+            } else if self.is_context && pos.into() > before_next {
                 // SAFETY: offsets generated by previous call to `InputRef::next` (or similar).
-                let span = unsafe { inp.input.span(before.offset..new_alt.pos) };
-                new_alt.err.in_context(self.label.clone(), span);
+                let span = unsafe { inp.input.span(before_offset..pos) };
+                err.in_context(self.label.clone(), span);
             }
+        };
+
+        if let Some(mut new_alt) = new_alt {
+            annotate(inp, new_alt.pos, &mut new_alt.err);
             inp.add_alt_err(new_alt.pos, new_alt.err);
         }
 
-        if self.is_context {
-            let before_offset = before.offset;
-            for err in inp.errors.secondary_errors_since(before.err_count) {
-                // SAFETY: offsets generated by previous call to `InputRef::next` (or similar).
-                let span = unsafe { inp.input.span(before_offset..err.pos) };
-                err.err.in_context(self.label.clone(), span);
-            }
+        let mut secondary = core::mem::take(&mut inp.errors.secondary);
+        for err in secondary.get_mut(before.err_count..).unwrap_or(&mut []) {
+            annotate(inp, err.pos, &mut err.err);
         }
+        inp.errors.secondary = secondary;
 
         res
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx).relabelled()
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate_where(
+        &self,
+        gen: &mut Generator<'_, I::Token>,
+        f: &dyn Fn(&O) -> bool,
+    ) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate_where(gen, f)
+    }
+
     go_extra!(O);
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn merged_contexts() {
+        let call = text::ident::<_, _, extra::Err<Rich<char, SimpleSpan, &str>>>()
+            .then(just("()"))
+            .labelled("call")
+            .as_context();
+        let index = text::ident()
+            .then(just("[]"))
+            .labelled("index")
+            .as_context();
+        let parser = just('=')
+            .ignore_then(choice((call, index)))
+            .labelled("assignment")
+            .as_context();
+
+        let errs = parser.parse("=foo").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(
+            errs[0].contexts().map(|(l, _)| *l).collect::<Vec<_>>(),
+            vec!["call", "index", "assignment"],
+        );
+    }
+
+    #[test]
+    fn labelled_secondary_errors() {
+        let item = text::int::<_, _, extra::Err<Rich<char, SimpleSpan, &str>>>(10)
+            .recover_with(via_parser(any().to("0")))
+            .labelled("number");
+        let list = item
+            .separated_by(just(','))
+            .collect::<Vec<_>>()
+            .delimited_by(just('['), just(']'))
+            .labelled("list")
+            .as_context();
+
+        let (out, errs) = list.parse("[1,x]").into_output_errors();
+        assert_eq!(out, Some(vec!["1", "0"]));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(
+            errs[0].expected().collect::<Vec<_>>(),
+            vec![&crate::error::RichPattern::Label("number")],
+        );
+        assert_eq!(
+            errs[0].contexts().map(|(l, _)| *l).collect::<Vec<_>>(),
+            vec!["list"],
+        );
+    }
+}
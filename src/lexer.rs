@@ -0,0 +1,930 @@
+//! A lexer built from token rules, combined with longest-match semantics.
+//!
+//! Lexing with [`choice`] tries each alternative in turn and takes the first that succeeds, so rules that are prefixes
+//! of one another (such as the keyword `if` and the identifier `iffy`) must be carefully ordered. A [`Lexer`] instead
+//! tries every rule at each position and takes the one that matches the most input (the 'longest match'). Ties are
+//! broken by priority and then by the order in which rules were added, so keywords can be given precedence over
+//! identifiers of the same length.
+//!
+//! Rules can be any parser: literals ([`just`]), regexes, [`text::ident`], or parsers of your own. Rules added with
+//! [`Lexer::skip`] match trivia, such as whitespace and comments, which is consumed but produces no token. Input that
+//! no rule matches is reported as an error and skipped, so a single invalid character doesn't prevent the rest of the
+//! input from being lexed.
+//!
+//! The output of a lexer is a [`Vec`] of tokens along with their spans, which can be turned into an input for a
+//! token-level parser with [`Input::spanned`].
+//!
+//! Some languages need to be lexed differently depending on context, such as strings containing interpolated
+//! expressions (`"Hello, ${name}!"`) or markup containing both text and nested tags. A lexer can be given modes (see
+//! [`LexerMode`]) that take over lexing when they start, producing a single token. A mode can push the lexer's own rules
+//! back onto the stack to lex a nested section of input (such as an interpolated expression) with
+//! [`ModeContext::lex_nested`], which may itself enter other modes, so modes can be nested to any depth. The
+//! [`Lexer::interpolated`] mode covers the common case of interpolated strings.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, lexer::lexer};
+//! #[derive(Clone, Debug, PartialEq)]
+//! enum Token<'a> {
+//!     If,
+//!     Ident(&'a str),
+//!     Num(u64),
+//!     Eq,
+//!     EqEq,
+//! }
+//!
+//! let lex = lexer::<_, _, extra::Err<Rich<char>>>()
+//!     .token(text::keyword("if").to(Token::If))
+//!     .token(text::ident().map(Token::Ident))
+//!     .token(text::int(10).from_str().unwrapped().map(Token::Num))
+//!     .token(just('=').to(Token::Eq))
+//!     .token(just("==").to(Token::EqEq))
+//!     .skip(text::whitespace().at_least(1))
+//!     .skip(just("//").then(any().and_is(just('\n').not()).repeated()));
+//!
+//! let (tokens, errs) = lex.parse("if iffy == 42 // comment\n$x = 1").into_output_errors();
+//! assert_eq!(
+//!     tokens.unwrap().into_iter().map(|(tok, _)| tok).collect::<Vec<_>>(),
+//!     [
+//!         Token::If,
+//!         Token::Ident("iffy"),
+//!         Token::EqEq,
+//!         Token::Num(42),
+//!         Token::Ident("x"),
+//!         Token::Eq,
+//!         Token::Num(1),
+//!     ],
+//! );
+//! // The invalid '$' was skipped
+//! assert_eq!(errs.len(), 1);
+//! assert_eq!(errs[0].span(), &SimpleSpan::from(25..26));
+//! ```
+
+use super::*;
+
+#[derive(Copy, Clone, PartialEq)]
+enum RuleKind {
+    Token,
+    Open,
+    Close,
+}
+
+struct Rule<'a, 'b, I: Input<'a>, T, E: ParserExtra<'a, I>> {
+    // Trivia rules produce `None`
+    parser: Boxed<'a, 'b, I, Option<T>, E>,
+    priority: i32,
+    kind: RuleKind,
+}
+
+impl<'a, 'b, I: Input<'a>, T, E: ParserExtra<'a, I>> Clone for Rule<'a, 'b, I, T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            priority: self.priority,
+            kind: self.kind,
+        }
+    }
+}
+
+/// A mode that a [`Lexer`] can switch into to lex part of its input differently, producing a single token. See
+/// [`Lexer::mode`].
+///
+/// # Examples
+///
+/// Raw strings, which end with a quote followed by as many `#`s as they began with, can't be lexed by a single rule:
+///
+/// ```
+/// use chumsky::{prelude::*, input::InputRef, lexer::{lexer, LexerMode, ModeContext}};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Token<'a> {
+///     Ident(&'a str),
+///     RawStr(&'a str),
+/// }
+///
+/// struct RawStr;
+///
+/// impl<'a, E: extra::ParserExtra<'a, &'a str>> LexerMode<'a, &'a str, Token<'a>, E> for RawStr {
+///     fn starts(&self, inp: &mut InputRef<'a, '_, &'a str, E>) -> bool {
+///         inp.check(just("r#")).is_ok()
+///     }
+///
+///     fn lex(
+///         &self,
+///         cx: &mut ModeContext<'a, '_, '_, '_, &'a str, Token<'a>, E>,
+///     ) -> Result<Token<'a>, E::Error> {
+///         let inp = cx.input();
+///         let before = inp.offset();
+///         let hashes = inp.parse(just('r').ignore_then(just('#').repeated().count()))?;
+///         inp.check(just('"'))?;
+///         let start = inp.offset();
+///         loop {
+///             let end = inp.save();
+///             if inp.check(just('"').then(just('#').repeated().exactly(hashes))).is_ok() {
+///                 return Ok(Token::RawStr(inp.slice(start..end.offset())));
+///             }
+///             inp.rewind(end);
+///             if inp.next().is_none() {
+///                 let open_span = inp.span(before..start);
+///                 cx.unclosed(open_span)?;
+///                 return Ok(Token::RawStr(cx.input().slice_from(start..)));
+///             }
+///         }
+///     }
+/// }
+///
+/// let lex = lexer::<_, _, extra::Err<Rich<char>>>()
+///     .token(text::ident().map(Token::Ident))
+///     .skip(text::whitespace().at_least(1))
+///     .mode(RawStr);
+///
+/// assert_eq!(
+///     lex.parse(r###"a r##"b"#c"## d"###).into_result().unwrap(),
+///     [
+///         (Token::Ident("a"), SimpleSpan::from(0..1)),
+///         (Token::RawStr(r##"b"#c"##), SimpleSpan::from(2..13)),
+///         (Token::Ident("d"), SimpleSpan::from(14..15)),
+///     ],
+/// );
+///
+/// // Unclosed raw strings are reported
+/// let (tokens, errs) = lex.parse(r##"r#"a"##).into_output_errors();
+/// assert_eq!(tokens.unwrap()[0].0, Token::RawStr("a"));
+/// assert_eq!(errs[0].span(), &SimpleSpan::from(0..3));
+/// ```
+pub trait LexerMode<'a, I: Input<'a>, T, E: ParserExtra<'a, I>> {
+    /// Check whether the mode starts at the current position of the input.
+    ///
+    /// Modes are checked in the order that they were added, before any of the lexer's rules. Any input consumed is
+    /// rewound afterwards, and a mode whose start matches no input is never entered, since it would never make
+    /// progress.
+    fn starts(&self, inp: &mut InputRef<'a, '_, I, E>) -> bool;
+
+    /// Lex the input of the mode, starting from the position at which [`LexerMode::starts`] was called, and produce a
+    /// token.
+    ///
+    /// Returning an error causes the lexer to fail with that error. Errors that can be recovered from (such as
+    /// unclosed delimiters) should instead be reported with [`ModeContext`], allowing lexing to continue.
+    fn lex(&self, cx: &mut ModeContext<'a, '_, '_, '_, I, T, E>) -> Result<T, E::Error>;
+}
+
+#[cfg(feature = "sync")]
+type DynMode<'a, 'b, I, T, E> = dyn LexerMode<'a, I, T, E> + Send + Sync + 'b;
+#[cfg(not(feature = "sync"))]
+type DynMode<'a, 'b, I, T, E> = dyn LexerMode<'a, I, T, E> + 'b;
+
+/// The state of a [`Lexer`] while in a mode, given to [`LexerMode::lex`].
+///
+/// The lexer keeps a stack of modes: [`ModeContext::lex_nested`] pushes the lexer's own rules onto it, lexing tokens
+/// (and entering any modes that start) until a closing delimiter that wasn't opened within the nested input is found.
+pub struct ModeContext<'a, 'b, 'parse, 'r, I: Input<'a>, T, E: ParserExtra<'a, I>> {
+    lexer: &'r Lexer<'a, 'b, I, T, E>,
+    inp: &'r mut InputRef<'a, 'parse, I, E>,
+}
+
+impl<'a, 'b, 'parse, 'r, I, T, E> ModeContext<'a, 'b, 'parse, 'r, I, T, E>
+where
+    I: Input<'a>,
+    T: 'a,
+    E: ParserExtra<'a, I>,
+{
+    /// Get the input that is being lexed.
+    pub fn input(&mut self) -> &mut InputRef<'a, 'parse, I, E> {
+        self.inp
+    }
+
+    /// Lex tokens using the lexer's rules and modes until the end of the input or a closing delimiter (see
+    /// [`Lexer::close_delimiter`]) that was not opened within the nested input, which is not consumed. See
+    /// [`ModeContext::close`].
+    pub fn lex_nested(&mut self) -> Result<Vec<(T, I::Span)>, E::Error> {
+        match self.lexer.lex::<Emit>(self.inp, true) {
+            Ok(tokens) => Ok(tokens),
+            Err(()) => Err(match self.inp.errors.alt.take() {
+                Some(alt) => alt.err,
+                None => self.halted(),
+            }),
+        }
+    }
+
+    /// Consume a closing delimiter, if one is next, returning whether one was found.
+    ///
+    /// After [`ModeContext::lex_nested`], no closing delimiter being found means that the end of the input was
+    /// reached.
+    pub fn close(&mut self) -> bool {
+        let before = self.inp.save();
+        let closed = match self.lexer.longest_match(self.inp) {
+            Some(i) if self.lexer.rules[i].kind == RuleKind::Close => {
+                self.lexer.rules[i].parser.go::<Check>(self.inp).is_ok()
+            }
+            _ => false,
+        };
+        if !closed {
+            self.inp.rewind(before);
+        }
+        closed
+    }
+
+    /// Report that a delimiter, opened at `open_span`, was not closed before the current position (see
+    /// [`Error::unclosed_delimiter`]).
+    ///
+    /// This counts as an error recovery: if no more recoveries are permitted (see [`ParseOptions`]), an error is
+    /// returned that should be returned from [`LexerMode::lex`].
+    pub fn unclosed(&mut self, open_span: I::Span) -> Result<(), E::Error> {
+        let at = self.inp.offset;
+        if !self.inp.errors.begin_recovery(at) {
+            return Err(self.halted());
+        }
+        let found = self.inp.peek_maybe();
+        let span = self.inp.span_since(self.inp.offset());
+        let err = E::Error::expected_found(None, found, span);
+        self.inp.emit(at, err.unclosed_delimiter(open_span));
+        Ok(())
+    }
+
+    /// Skip the next token of input, reporting it as unexpected.
+    ///
+    /// Like [`ModeContext::unclosed`], this counts as an error recovery.
+    pub fn skip_invalid(&mut self) -> Result<(), E::Error> {
+        let before = self.inp.offset();
+        if !self.inp.errors.begin_recovery(before.offset) {
+            return Err(self.halted());
+        }
+        let found = self.inp.next_maybe_inner().1.map(Into::into);
+        let span = self.inp.span_since(before);
+        self.inp
+            .emit(before.offset, E::Error::expected_found(None, found, span));
+        self.inp.mark_recovered(before.offset);
+        Ok(())
+    }
+
+    // The error to fail with once lexing has been halted by `ParseOptions`. It is replaced by the error describing the
+    // halt when parsing finishes.
+    fn halted(&self) -> E::Error {
+        E::Error::too_many_errors(self.inp.span_since(self.inp.offset()))
+    }
+}
+
+/// A parser that splits its input into tokens using a set of rules. See the [module-level documentation](self).
+///
+/// The output type of this parser is `Vec<(T, I::Span)>`: the tokens produced by each rule, along with their spans.
+pub struct Lexer<'a, 'b, I: Input<'a>, T, E: ParserExtra<'a, I> = extra::Default> {
+    rules: Vec<Rule<'a, 'b, I, T, E>>,
+    modes: Vec<RefC<DynMode<'a, 'b, I, T, E>>>,
+}
+
+impl<'a, 'b, I: Input<'a>, T, E: ParserExtra<'a, I>> Clone for Lexer<'a, 'b, I, T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            rules: self.rules.clone(),
+            modes: self.modes.clone(),
+        }
+    }
+}
+
+impl<'a, 'b, I: Input<'a>, T, E: ParserExtra<'a, I>> fmt::Debug for Lexer<'a, 'b, I, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tuple = f.debug_tuple("Lexer");
+        for rule in &self.rules {
+            tuple.field(&rule.parser);
+        }
+        for _ in &self.modes {
+            tuple.field(&format_args!("Mode(..)"));
+        }
+        tuple.finish()
+    }
+}
+
+/// Create a [`Lexer`] with no rules.
+///
+/// Rules can be added with [`Lexer::token`], [`Lexer::token_with_priority`] and [`Lexer::skip`].
+pub const fn lexer<'a, 'b, I: Input<'a>, T, E: ParserExtra<'a, I>>() -> Lexer<'a, 'b, I, T, E> {
+    Lexer {
+        rules: Vec::new(),
+        modes: Vec::new(),
+    }
+}
+
+impl<'a, 'b, I, T, E> Lexer<'a, 'b, I, T, E>
+where
+    I: Input<'a>,
+    T: 'a,
+    E: ParserExtra<'a, I>,
+{
+    fn rule<P>(mut self, parser: P, priority: i32, kind: RuleKind) -> Self
+    where
+        T: 'b,
+        P: Parser<'a, I, T, E> + MaybeSync + 'a + 'b,
+    {
+        self.rules.push(Rule {
+            parser: Parser::boxed(parser.map(Some)),
+            priority,
+            kind,
+        });
+        self
+    }
+
+    /// Add a rule that produces a token, with a priority of `0`.
+    pub fn token<P>(self, parser: P) -> Self
+    where
+        T: 'b,
+        P: Parser<'a, I, T, E> + MaybeSync + 'a + 'b,
+    {
+        self.token_with_priority(parser, 0)
+    }
+
+    /// Add a rule that produces a token, with the given priority.
+    ///
+    /// When several rules match the same (longest) length of input, the rule with the highest priority wins. Rules with
+    /// the same priority are preferred in the order that they were added.
+    pub fn token_with_priority<P>(self, parser: P, priority: i32) -> Self
+    where
+        T: 'b,
+        P: Parser<'a, I, T, E> + MaybeSync + 'a + 'b,
+    {
+        self.rule(parser, priority, RuleKind::Token)
+    }
+
+    /// Add a rule that matches trivia (such as whitespace or comments), which is consumed without producing a token.
+    ///
+    /// Trivia rules take part in longest-match in the same way as other rules, with a priority of `0`.
+    pub fn skip<P, O>(mut self, parser: P) -> Self
+    where
+        T: 'b,
+        O: 'a + 'b,
+        P: Parser<'a, I, O, E> + MaybeSync + 'a + 'b,
+    {
+        self.rules.push(Rule {
+            parser: Parser::boxed(parser.ignored().map(|()| None)),
+            priority: 0,
+            kind: RuleKind::Token,
+        });
+        self
+    }
+
+    /// Add a rule that produces an opening delimiter token (such as `{`), with a priority of `0`.
+    ///
+    /// Opening and closing delimiters are counted so that the lexer knows where an interpolated expression ends: see
+    /// [`Lexer::interpolated`]. Otherwise, they behave like any other token.
+    pub fn open_delimiter<P>(self, parser: P) -> Self
+    where
+        T: 'b,
+        P: Parser<'a, I, T, E> + MaybeSync + 'a + 'b,
+    {
+        self.rule(parser, 0, RuleKind::Open)
+    }
+
+    /// Add a rule that produces a closing delimiter token (such as `}`), with a priority of `0`.
+    ///
+    /// See [`Lexer::open_delimiter`].
+    pub fn close_delimiter<P>(self, parser: P) -> Self
+    where
+        T: 'b,
+        P: Parser<'a, I, T, E> + MaybeSync + 'a + 'b,
+    {
+        self.rule(parser, 0, RuleKind::Close)
+    }
+
+    /// Add a mode, which takes over lexing when it starts and produces a single token. See [`LexerMode`].
+    ///
+    /// Modes are tried before any other rule, in the order that they were added.
+    pub fn mode<Mo>(mut self, mode: Mo) -> Self
+    where
+        Mo: LexerMode<'a, I, T, E> + MaybeSync + 'b,
+    {
+        self.modes.push(RefC::new(mode));
+        self
+    }
+
+    /// Add a mode for interpolated strings, such as `"Hello, ${name}!"`, which produces a single token made up of
+    /// [`Part`]s. See [`Lexer::mode`].
+    ///
+    /// The mode is entered when `start` matches (and consumes input). Within the string, `segment` is used to
+    /// lex literal text (including any escape sequences) until `end` is found. When `open` matches, the lexer switches
+    /// back to lexing tokens until it finds a closing delimiter (see [`Lexer::close_delimiter`]) that was not opened
+    /// within the interpolation. Interpolations may themselves contain interpolated strings.
+    ///
+    /// A string or interpolation that is not closed before the end of the input is reported as an unclosed delimiter
+    /// (see [`Error::unclosed_delimiter`]), as is any input that `segment` cannot lex, which is then skipped.
+    ///
+    /// The parts of the string are passed to `f` to produce the token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, lexer::{lexer, Part}};
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Token<'a> {
+    ///     Ident(&'a str),
+    ///     LBrace,
+    ///     RBrace,
+    ///     Str(Vec<Part<&'a str, Token<'a>, SimpleSpan>>),
+    /// }
+    ///
+    /// let lex = lexer::<_, _, extra::Err<Rich<char>>>()
+    ///     .token(text::ident().map(Token::Ident))
+    ///     .open_delimiter(just('{').to(Token::LBrace))
+    ///     .close_delimiter(just('}').to(Token::RBrace))
+    ///     .skip(text::whitespace().at_least(1))
+    ///     .interpolated(
+    ///         just('"'),
+    ///         just('"'),
+    ///         just("${"),
+    ///         none_of("\"$").repeated().at_least(1).slice(),
+    ///         Token::Str,
+    ///     );
+    ///
+    /// let tokens = lex.parse(r#""Hi ${ {name} }!""#).into_result().unwrap();
+    /// assert_eq!(
+    ///     tokens[0].0,
+    ///     Token::Str(vec![
+    ///         Part::Segment("Hi "),
+    ///         Part::Expr(vec![
+    ///             (Token::LBrace, SimpleSpan::from(7..8)),
+    ///             (Token::Ident("name"), SimpleSpan::from(8..12)),
+    ///             (Token::RBrace, SimpleSpan::from(12..13)),
+    ///         ]),
+    ///         Part::Segment("!"),
+    ///     ]),
+    /// );
+    ///
+    /// // Unclosed interpolations are reported
+    /// let errs = lex.parse(r#""Hi ${name"#).into_errors();
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(4..6));
+    /// ```
+    pub fn interpolated<A, B, C, OA, OB, OC, S, P, F>(
+        self,
+        start: A,
+        end: B,
+        open: C,
+        segment: P,
+        f: F,
+    ) -> Self
+    where
+        T: 'b,
+        A: Parser<'a, I, OA, E> + MaybeSync + 'b,
+        B: Parser<'a, I, OB, E> + MaybeSync + 'b,
+        C: Parser<'a, I, OC, E> + MaybeSync + 'b,
+        P: Parser<'a, I, S, E> + MaybeSync + 'b,
+        F: Fn(Vec<Part<S, T, I::Span>>) -> T + MaybeSync + 'b,
+        OA: 'b,
+        OB: 'b,
+        OC: 'b,
+        S: 'b,
+    {
+        self.mode(Interpolated {
+            start,
+            end,
+            open,
+            segment,
+            f,
+            phantom: EmptyPhantom::new(),
+        })
+    }
+
+    // Find the rule that matches the most input at the current position, without consuming it
+    fn longest_match(&self, inp: &mut InputRef<'a, '_, I, E>) -> Option<usize> {
+        let before = inp.save();
+        let mut best: Option<(usize, I::Offset)> = None;
+        for (i, rule) in self.rules.iter().enumerate() {
+            // Rules that match no input are ignored: they would never make progress
+            if rule.parser.go::<Check>(inp).is_ok() && inp.offset > before.offset {
+                let better = match best {
+                    Some((j, end)) => {
+                        inp.offset > end
+                            || (inp.offset == end && rule.priority > self.rules[j].priority)
+                    }
+                    None => true,
+                };
+                if better {
+                    best = Some((i, inp.offset));
+                }
+            }
+            inp.rewind(before);
+        }
+        best.map(|(i, _)| i)
+    }
+
+    // Lex tokens until the end of input or, if `nested`, until a closing delimiter that has not been opened
+    fn lex<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        nested: bool,
+    ) -> PResult<M, Vec<(T, I::Span)>> {
+        let mut tokens = M::bind(Vec::new);
+        // The start of a run of input that no rule matched, along with its first token
+        let mut invalid = None;
+        let mut depth = 0usize;
+        loop {
+            let before = inp.offset();
+
+            if let Some(mode) = self
+                .modes
+                .iter()
+                .find(|mode| Self::mode_starts(&***mode, inp))
+            {
+                self.flush_invalid(inp, &mut invalid, before);
+                match mode.lex(&mut ModeContext { lexer: self, inp }) {
+                    Ok(tok) => M::combine_mut(&mut tokens, M::bind(|| tok), |tokens, tok| {
+                        tokens.push((tok, inp.span_since(before)))
+                    }),
+                    Err(err) => {
+                        inp.add_alt_err(before.offset, err);
+                        return Err(());
+                    }
+                }
+                continue;
+            }
+
+            let rule = self.longest_match(inp);
+
+            if rule.is_some() || inp.peek_maybe().is_none() {
+                self.flush_invalid(inp, &mut invalid, before);
+            }
+
+            match rule {
+                Some(i) => {
+                    match self.rules[i].kind {
+                        RuleKind::Token => {}
+                        RuleKind::Open => depth += 1,
+                        RuleKind::Close if depth == 0 && nested => break Ok(tokens),
+                        RuleKind::Close => depth = depth.saturating_sub(1),
+                    }
+                    let tok = self.rules[i].parser.go::<M>(inp)?;
+                    M::combine_mut(&mut tokens, tok, |tokens, tok| {
+                        if let Some(tok) = tok {
+                            tokens.push((tok, inp.span_since(before)));
+                        }
+                    });
+                }
+                None => match inp.next_maybe_inner() {
+                    (_, Some(tok)) => {
+                        if invalid.is_none() {
+                            if !inp.errors.begin_recovery(before.offset) {
+                                return Err(());
+                            }
+                            invalid = Some((before, tok.into()));
+                        }
+                    }
+                    (_, None) => break Ok(tokens),
+                },
+            }
+        }
+    }
+
+    // Report a run of input that no rule matched, ending at `end` (the current position), as skipped by recovery
+    fn flush_invalid<'parse>(
+        &self,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        invalid: &mut Option<(input::Offset<'a, 'parse, I>, MaybeRef<'a, I::Token>)>,
+        end: input::Offset<'a, 'parse, I>,
+    ) {
+        if let Some((start, found)) = invalid.take() {
+            let span = inp.span(start..end);
+            inp.emit(
+                start.offset,
+                E::Error::expected_found(None, Some(found), span),
+            );
+            inp.mark_recovered(start.offset);
+        }
+    }
+
+    // Check whether a mode starts at the current position, without consuming any input
+    fn mode_starts(mode: &DynMode<'a, 'b, I, T, E>, inp: &mut InputRef<'a, '_, I, E>) -> bool {
+        let before = inp.save();
+        // As with rules, a start that matches no input is ignored: the mode would never make progress
+        let starts = mode.starts(inp) && inp.offset > before.offset;
+        inp.rewind(before);
+        starts
+    }
+}
+
+impl<'a, 'b, I, T, E> ParserSealed<'a, I, Vec<(T, I::Span)>, E> for Lexer<'a, 'b, I, T, E>
+where
+    I: Input<'a>,
+    T: 'a,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, Vec<(T, I::Span)>> {
+        self.lex::<M>(inp, false)
+    }
+
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        // Lexing consumes any amount of input, including none at all
+        Summary::token(None).repeated(0).restricted()
+    }
+
+    go_extra!(Vec<(T, I::Span)>);
+}
+
+/// A part of an interpolated string. See [`Lexer::interpolated`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Part<S, T, Sp> {
+    /// A segment of literal text, as produced by the segment parser.
+    Segment(S),
+    /// The tokens of an interpolated expression, along with their spans.
+    Expr(Vec<(T, Sp)>),
+}
+
+struct Interpolated<A, B, C, OA, OB, OC, S, P, F> {
+    start: A,
+    end: B,
+    open: C,
+    segment: P,
+    f: F,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(OA, OB, OC, S)>,
+}
+
+impl<'a, A, B, C, OA, OB, OC, P, F, I, T, E, S> LexerMode<'a, I, T, E>
+    for Interpolated<A, B, C, OA, OB, OC, S, P, F>
+where
+    I: Input<'a>,
+    T: 'a,
+    E: ParserExtra<'a, I>,
+    A: Parser<'a, I, OA, E>,
+    B: Parser<'a, I, OB, E>,
+    C: Parser<'a, I, OC, E>,
+    P: Parser<'a, I, S, E>,
+    F: Fn(Vec<Part<S, T, I::Span>>) -> T,
+{
+    fn starts(&self, inp: &mut InputRef<'a, '_, I, E>) -> bool {
+        inp.check(&self.start).is_ok()
+    }
+
+    fn lex(&self, cx: &mut ModeContext<'a, '_, '_, '_, I, T, E>) -> Result<T, E::Error> {
+        let before = cx.inp.offset();
+        cx.inp.check(&self.start)?;
+        let start_span = cx.inp.span_since(before);
+        let mut parts = Vec::new();
+        loop {
+            let before_part = cx.inp.save();
+            if cx.inp.check(&self.end).is_ok() {
+                break;
+            }
+            cx.inp.rewind(before_part);
+
+            if cx.inp.check(&self.open).is_ok() {
+                let open_span = cx.inp.span_since(before_part.offset());
+                parts.push(Part::Expr(cx.lex_nested()?));
+                if !cx.close() {
+                    // The nested lexer only stops early at the end of input, so the string is unclosed too
+                    cx.unclosed(open_span)?;
+                    cx.unclosed(start_span)?;
+                    break;
+                }
+                continue;
+            }
+            cx.inp.rewind(before_part);
+
+            match cx.inp.parse(&self.segment) {
+                Ok(segment) if cx.inp.offset > before_part.offset => {
+                    parts.push(Part::Segment(segment))
+                }
+                _ => {
+                    cx.inp.rewind(before_part);
+                    if cx.inp.peek_maybe().is_none() {
+                        cx.unclosed(start_span)?;
+                        break;
+                    }
+                    // Skip input that cannot be lexed as part of the string
+                    cx.skip_invalid()?;
+                }
+            }
+        }
+        Ok((self.f)(parts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Token<'a> {
+        Ident(&'a str),
+        Let,
+        Num(&'a str),
+        Dot,
+        Range,
+    }
+
+    fn lex<'a>() -> Lexer<'a, 'a, &'a str, Token<'a>, extra::Err<Rich<'a, char>>> {
+        lexer()
+            .token(text::ident().map(Token::Ident))
+            .token_with_priority(just("let").to(Token::Let), 1)
+            .token(text::int(10).slice().map(Token::Num))
+            .token(just('.').to(Token::Dot))
+            .token(just("..").to(Token::Range))
+            .skip(text::whitespace())
+    }
+
+    #[test]
+    fn longest_match() {
+        let tokens = lex().parse("let letter 1..2").into_result().unwrap();
+        assert_eq!(
+            tokens,
+            [
+                (Token::Let, SimpleSpan::from(0..3)),
+                (Token::Ident("letter"), SimpleSpan::from(4..10)),
+                (Token::Num("1"), SimpleSpan::from(11..12)),
+                (Token::Range, SimpleSpan::from(12..14)),
+                (Token::Num("2"), SimpleSpan::from(14..15)),
+            ],
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        let (tokens, errs) = lex().parse("a ?? b . ~").into_output_errors();
+        assert_eq!(
+            tokens
+                .unwrap()
+                .into_iter()
+                .map(|(t, _)| t)
+                .collect::<Vec<_>>(),
+            [Token::Ident("a"), Token::Ident("b"), Token::Dot],
+        );
+        // Consecutive invalid characters are reported together
+        assert_eq!(
+            errs.iter().map(|e| *e.span()).collect::<Vec<_>>(),
+            [SimpleSpan::from(2..4), SimpleSpan::from(9..10)],
+        );
+        assert_eq!(errs[0].found(), Some(&'?'));
+
+        // Skipping invalid input counts as a recovery
+        let res = lex().parse_with_options("a ?? b . ~", ParseOptions::new().max_recoveries(1));
+        assert!(!res.has_output());
+        let errs = res.into_errors();
+        assert_eq!(errs.len(), 2);
+        assert_eq!(
+            errs[1],
+            Rich::custom((9..9).into(), "too many errors, parsing halted")
+        );
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum IToken<'a> {
+        Ident(&'a str),
+        LBrace,
+        RBrace,
+        Str(Vec<Part<&'a str, IToken<'a>, SimpleSpan>>),
+    }
+
+    fn interp_lex<'a>() -> Lexer<'a, 'a, &'a str, IToken<'a>, extra::Err<Rich<'a, char>>> {
+        lexer()
+            .token(text::ident().map(IToken::Ident))
+            .open_delimiter(just('{').to(IToken::LBrace))
+            .close_delimiter(just('}').to(IToken::RBrace))
+            .skip(text::whitespace())
+            .interpolated(
+                just('"'),
+                just('"'),
+                just("${"),
+                choice((
+                    none_of("\\\"$").repeated().at_least(1).slice(),
+                    just("\\$").to("$"),
+                )),
+                IToken::Str,
+            )
+    }
+
+    #[test]
+    fn interpolation() {
+        let strip = |tokens: Vec<(IToken<'static>, SimpleSpan)>| {
+            tokens.into_iter().map(|(t, _)| t).collect::<Vec<_>>()
+        };
+        let tokens = interp_lex()
+            .parse(r#"{ "a\$${"b${c}" {}}d" } }"#)
+            .into_result()
+            .unwrap();
+        assert_eq!(
+            strip(tokens),
+            [
+                IToken::LBrace,
+                IToken::Str(vec![
+                    Part::Segment("a"),
+                    Part::Segment("$"),
+                    Part::Expr(vec![
+                        (
+                            IToken::Str(vec![
+                                Part::Segment("b"),
+                                Part::Expr(vec![(IToken::Ident("c"), SimpleSpan::from(12..13))]),
+                            ]),
+                            SimpleSpan::from(8..15),
+                        ),
+                        (IToken::LBrace, SimpleSpan::from(16..17)),
+                        (IToken::RBrace, SimpleSpan::from(17..18)),
+                    ]),
+                    Part::Segment("d"),
+                ]),
+                IToken::RBrace,
+                // Unbalanced closing delimiters outside of interpolations are just tokens
+                IToken::RBrace,
+            ],
+        );
+    }
+
+    #[test]
+    fn unclosed_interpolation() {
+        let (tokens, errs) = interp_lex().parse(r#""a ${b "c"#).into_output_errors();
+        assert_eq!(
+            tokens.unwrap()[0].0,
+            IToken::Str(vec![
+                Part::Segment("a "),
+                Part::Expr(vec![
+                    (IToken::Ident("b"), SimpleSpan::from(5..6)),
+                    (
+                        IToken::Str(vec![Part::Segment("c")]),
+                        SimpleSpan::from(7..9)
+                    ),
+                ]),
+            ]),
+        );
+        // The inner string, the interpolation, and the outer string are all unclosed
+        assert_eq!(
+            errs.iter().map(|e| *e.span()).collect::<Vec<_>>(),
+            [
+                SimpleSpan::from(7..8),
+                SimpleSpan::from(3..5),
+                SimpleSpan::from(0..1)
+            ],
+        );
+        assert_eq!(errs[0].to_string(), "unclosed delimiter");
+
+        // Input that can't be lexed as part of a string is skipped
+        let (tokens, errs) = interp_lex().parse(r#""a$b""#).into_output_errors();
+        assert_eq!(
+            tokens.unwrap()[0].0,
+            IToken::Str(vec![Part::Segment("a"), Part::Segment("b")]),
+        );
+        assert_eq!(errs[0].span(), &SimpleSpan::from(2..3));
+    }
+
+    #[test]
+    fn unclosed_interpolation_errors() {
+        let lex = lexer::<_, _, extra::Err<Simple<char>>>()
+            .token(text::ident().map(IToken::Ident))
+            .close_delimiter(just('}').to(IToken::RBrace))
+            .skip(text::whitespace())
+            .interpolated(
+                just('"'),
+                just('"'),
+                just("${"),
+                none_of("\"$").repeated().at_least(1).slice(),
+                IToken::Str,
+            );
+        // Each unclosed delimiter is reported where the end of input was found, rather than with an earlier error
+        let errs = lex.parse(r#"abc "x ${ def"#).into_errors();
+        assert_eq!(
+            errs.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            [
+                "found end of input at 13..13",
+                "found end of input at 13..13"
+            ],
+        );
+    }
+
+    #[test]
+    fn empty_mode_start() {
+        // A mode whose start matches no input is never entered
+        let lex = lexer::<_, _, extra::Err<Rich<char>>>()
+            .token(text::ident().map(IToken::Ident))
+            .skip(text::whitespace().at_least(1))
+            .interpolated(
+                just('"').or_not(),
+                just('"'),
+                just("${"),
+                none_of("\"$").repeated().at_least(1).slice(),
+                IToken::Str,
+            );
+        let tokens = lex.parse("a b").into_result().unwrap();
+        assert_eq!(
+            tokens,
+            [
+                (IToken::Ident("a"), SimpleSpan::from(0..1)),
+                (IToken::Ident("b"), SimpleSpan::from(2..3)),
+            ],
+        );
+    }
+
+    #[test]
+    fn spanned_tokens() {
+        let src = "let x";
+        let tokens = lex().parse(src).into_result().unwrap();
+        let parser = just::<_, _, extra::Err<Simple<_>>>(Token::Let).ignore_then(
+            select! { Token::Ident(x) => x }.map_with_span(|x, span: SimpleSpan| (x, span)),
+        );
+        assert_eq!(
+            parser
+                .parse(tokens.as_slice().spanned((src.len()..src.len()).into()))
+                .into_result(),
+            Ok(("x", SimpleSpan::from(4..5))),
+        );
+    }
+}
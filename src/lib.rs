@@ -46,6 +46,7 @@ macro_rules! go_cfg_extra {
     };
 }
 
+pub mod analysis;
 mod blanket;
 pub mod combinator;
 pub mod container;
@@ -53,13 +54,23 @@ pub mod error;
 #[cfg(feature = "extension")]
 pub mod extension;
 pub mod extra;
+#[cfg(feature = "arbitrary")]
+pub mod generate;
 #[cfg(docsrs)]
 pub mod guide;
 pub mod input;
+pub mod kind;
 #[cfg(feature = "label")]
 pub mod label;
+pub mod lexer;
+#[cfg(feature = "logos")]
+pub mod logos;
+#[cfg(feature = "lsp")]
+pub mod lsp;
 pub mod primitive;
 mod private;
+#[cfg(feature = "profile")]
+pub mod profile;
 pub mod recovery;
 pub mod recursive;
 #[cfg(feature = "regex")]
@@ -67,6 +78,7 @@ pub mod regex;
 pub mod span;
 mod stream;
 pub mod text;
+pub mod token_tree;
 pub mod util;
 
 /// Commonly used functions, traits and types.
@@ -77,11 +89,13 @@ pub mod prelude {
     #[cfg(feature = "regex")]
     pub use super::regex::regex;
     pub use super::{
-        error::{Cheap, EmptyErr, Error as _, Rich, Simple},
+        error::{Cheap, EmptyErr, Error as _, Rich, Severity, Simple},
         extra,
         input::Input,
         primitive::{any, choice, custom, empty, end, group, just, map_ctx, none_of, one_of, todo},
-        recovery::{nested_delimiters, skip_then_retry_until, skip_until, via_parser},
+        recovery::{
+            nested_delimiters, repair, skip_then_retry_until, skip_until, sync_to, via_parser,
+        },
         recursive::{recursive, Recursive},
         span::{SimpleSpan, Span as _},
         text, Boxed, ConfigIterParser, ConfigParser, IterParser, ParseResult, Parser,
@@ -96,7 +110,7 @@ use core::{
     cell::{Cell, RefCell, UnsafeCell},
     cmp::{Eq, Ordering},
     fmt,
-    hash::Hash,
+    hash::{Hash, Hasher},
     marker::PhantomData,
     mem::MaybeUninit,
     ops::{Range, RangeFrom},
@@ -105,21 +119,25 @@ use core::{
 };
 use hashbrown::HashMap;
 
+#[cfg(feature = "arbitrary")]
+use self::generate::{GenerateError, Generator};
 #[cfg(feature = "label")]
 use self::label::{LabelError, Labelled};
 use self::{
+    analysis::{Analyzer, Problem, Summary},
     combinator::*,
     container::*,
     error::Error,
     extra::ParserExtra,
-    input::{BorrowInput, Emitter, ExactSizeInput, InputRef, SliceInput, StrInput, ValueInput},
+    input::{BorrowInput, Emitter, ExactSizeInput, InputRef, SliceInput, TextInput, ValueInput},
+    kind::{KindSet, TokenKind},
     prelude::*,
     primitive::Any,
     private::{
         Check, ConfigIterParserSealed, ConfigParserSealed, Emit, IPResult, IterParserSealed,
         Located, MaybeUninitExt, Mode, PResult, ParserSealed, Sealed,
     },
-    recovery::{RecoverWith, Strategy},
+    recovery::{RecoverWith, RecoveryInfo, Strategy},
     span::Span,
     text::*,
     util::{MaybeMut, MaybeRef},
@@ -184,15 +202,27 @@ use sync::{DynParser, MaybeSync, RefC, RefW};
 /// The result of running a [`Parser`]. Can be converted into a [`Result`] via
 /// [`ParseResult::into_result`] for when you only care about success or failure, or into distinct
 /// error and output via [`ParseResult::into_output_errors`]
+///
+/// Warnings emitted during parsing (see [`Emitter::warn`] and [`InputRef::warn`]) are kept separately from errors and
+/// never cause parsing to be considered a failure. They can be accessed with [`ParseResult::warnings`].
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ParseResult<T, E> {
     output: Option<T>,
     errs: Vec<E>,
+    warns: Vec<E>,
+    #[cfg(feature = "profile")]
+    profile: Option<profile::Profile>,
 }
 
 impl<T, E> ParseResult<T, E> {
-    pub(crate) fn new(output: Option<T>, errs: Vec<E>) -> ParseResult<T, E> {
-        ParseResult { output, errs }
+    pub(crate) fn new(output: Option<T>, errs: Vec<E>, warns: Vec<E>) -> ParseResult<T, E> {
+        ParseResult {
+            output,
+            errs,
+            warns,
+            #[cfg(feature = "profile")]
+            profile: None,
+        }
     }
 
     /// Whether this result contains output
@@ -215,6 +245,32 @@ impl<T, E> ParseResult<T, E> {
         self.errs.iter()
     }
 
+    /// Whether this result has any warnings
+    pub fn has_warnings(&self) -> bool {
+        !self.warns.is_empty()
+    }
+
+    /// Get an iterator over the warnings emitted while parsing. The iterator will be empty if there are no warnings.
+    pub fn warnings(&self) -> impl ExactSizeIterator<Item = &E> {
+        self.warns.iter()
+    }
+
+    /// Get an iterator over all errors and warnings of this result, alongside their [`Severity`].
+    ///
+    /// Errors are yielded before warnings.
+    pub fn diagnostics(&self) -> impl Iterator<Item = (Severity, &E)> {
+        self.errs
+            .iter()
+            .map(|err| (Severity::Error, err))
+            .chain(self.warns.iter().map(|warn| (Severity::Warning, warn)))
+    }
+
+    /// Get the statistics recorded while parsing, if profiling was enabled with [`ParseOptions::profile`].
+    #[cfg(feature = "profile")]
+    pub fn profile(&self) -> Option<&profile::Profile> {
+        self.profile.as_ref()
+    }
+
     /// Convert this `ParseResult` into an option containing the output, if any exists
     pub fn into_output(self) -> Option<T> {
         self.output
@@ -232,6 +288,12 @@ impl<T, E> ParseResult<T, E> {
         (self.output, self.errs)
     }
 
+    /// Convert this `ParseResult` into a vector containing any warnings. The vector will be empty if there were no
+    /// warnings.
+    pub fn into_warnings(self) -> Vec<E> {
+        self.warns
+    }
+
     /// Convert this `ParseResult` into a standard `Result`. This discards output if parsing generated any errors,
     /// matching the old behavior of [`Parser::parse`].
     pub fn into_result(self) -> Result<T, Vec<E>> {
@@ -243,6 +305,58 @@ impl<T, E> ParseResult<T, E> {
     }
 }
 
+/// Options that control the behaviour of a parse. See [`Parser::parse_with_options`].
+///
+/// By default, no limits are imposed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    max_errors: Option<usize>,
+    max_recoveries: Option<usize>,
+    #[cfg(feature = "profile")]
+    profile: bool,
+}
+
+impl ParseOptions {
+    /// Create a new set of options with no limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Halt parsing once the given number of errors have been produced.
+    ///
+    /// After the limit has been reached, error recovery strategies (see [`Parser::recover_with`]) no longer attempt
+    /// recovery, so parsing stops at the next syntax error. That error is then replaced by a final
+    /// [`Error::too_many_errors`] error.
+    pub fn max_errors(self, max_errors: usize) -> Self {
+        Self {
+            max_errors: Some(max_errors),
+            ..self
+        }
+    }
+
+    /// Halt parsing once error recovery has been attempted the given number of times, whether or not the attempts
+    /// were successful.
+    ///
+    /// This is useful for bounding the time spent on pathological inputs, since each recovery attempt may itself
+    /// involve a lot of backtracking. Halting behaves as with [`ParseOptions::max_errors`].
+    pub fn max_recoveries(self, max_recoveries: usize) -> Self {
+        Self {
+            max_recoveries: Some(max_recoveries),
+            ..self
+        }
+    }
+
+    /// Record statistics about named parsers (see [`Parser::named`]) while parsing, making them available through
+    /// [`ParseResult::profile`]. See the [`profile`] module for more information.
+    #[cfg(feature = "profile")]
+    pub fn profile(self) -> Self {
+        Self {
+            profile: true,
+            ..self
+        }
+    }
+}
+
 /// A trait implemented by parsers.
 ///
 /// Parsers take inputs of type `I` (implementing [`Input`]) and attempt to parse them into a value of type `O`. In
@@ -299,24 +413,99 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// Although the signature of this function looks complicated, it's simpler than you think! You can pass a
     /// [`&[T]`], a [`&str`], [`Stream`], or anything implementing [`Input`] to it.
     fn parse_with_state(&self, input: I, state: &mut E::State) -> ParseResult<O, E::Error>
+    where
+        Self: Sized,
+        I: Input<'a>,
+        E::Context: Default,
+    {
+        self.parse_with_state_and_options(input, state, ParseOptions::default())
+    }
+
+    /// Parse a stream of tokens with the given [`ParseOptions`], yielding an output if possible, and any errors
+    /// encountered along the way.
+    ///
+    /// This is useful for bounding the amount of work performed on pathological inputs when aggressive error recovery
+    /// is in use. If one of the limits is reached and parsing halts as a result, the output will be `None` and the
+    /// last error will be an [`Error::too_many_errors`] error.
+    ///
+    /// If you want to include non-default state, use [`Parser::parse_with_state_and_options`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, ParseOptions};
+    /// let stmt = text::int::<_, _, extra::Err<Rich<char>>>(10)
+    ///     .then_ignore(just(';'))
+    ///     .recover_with(skip_then_retry_until(any().ignored(), end()))
+    ///     .padded();
+    /// let stmts = stmt.repeated().collect::<Vec<_>>();
+    ///
+    /// let res = stmts.parse_with_options("1; x2; y3; z4; 5;", ParseOptions::new().max_errors(2));
+    /// let errs = res.into_errors();
+    /// assert_eq!(errs.len(), 3);
+    /// assert_eq!(errs[2].to_string(), "too many errors, parsing halted");
+    /// ```
+    fn parse_with_options(&self, input: I, options: ParseOptions) -> ParseResult<O, E::Error>
+    where
+        Self: Sized,
+        I: Input<'a>,
+        E::State: Default,
+        E::Context: Default,
+    {
+        self.parse_with_state_and_options(input, &mut E::State::default(), options)
+    }
+
+    /// Parse a stream of tokens with the given state and [`ParseOptions`], yielding an output if possible, and any
+    /// errors encountered along the way.
+    ///
+    /// See [`Parser::parse_with_state`] and [`Parser::parse_with_options`].
+    fn parse_with_state_and_options(
+        &self,
+        input: I,
+        state: &mut E::State,
+        options: ParseOptions,
+    ) -> ParseResult<O, E::Error>
     where
         Self: Sized,
         I: Input<'a>,
         E::Context: Default,
     {
         let mut own = InputOwn::new_state(input, state);
+        own.errors.options = options;
+        #[cfg(feature = "profile")]
+        if options.profile {
+            own.errors.profiler = Some(profile::Profiler::default());
+        }
         let mut inp = own.as_ref_start();
         let res = self.then_ignore(end()).go::<Emit>(&mut inp);
         let alt = inp.errors.alt.take();
-        let mut errs = own.into_errs();
+        // Halting only explains the failure if parsing got no further than the token it halted at, which might not be
+        // the case if the halt was backtracked out of
+        let halted_at = inp.errors.furthest_halt.filter(|at| {
+            // SAFETY: offset was generated by previous call to `Input::next`
+            let (next, _) = unsafe { inp.input.next_maybe(*at) };
+            !matches!(&alt, Some(alt) if alt.pos > next)
+        });
+        // SAFETY: offset was generated by previous call to `Input::next`
+        let halted = halted_at.map(|at| unsafe { inp.input.span(at..at) });
+        #[cfg(feature = "profile")]
+        let profile = own.errors.profiler.take().map(profile::Profiler::finish);
+        let (mut errs, warns) = own.into_errs_and_warnings();
         let out = match res {
             Ok(out) => Some(out),
             Err(()) => {
-                errs.push(alt.expect("error but no alt?").err);
+                errs.push(match halted {
+                    Some(span) => E::Error::too_many_errors(span),
+                    None => alt.expect("error but no alt?").err,
+                });
                 None
             }
         };
-        ParseResult::new(out, errs)
+        ParseResult {
+            #[cfg(feature = "profile")]
+            profile,
+            ..ParseResult::new(out, errs, warns)
+        }
     }
 
     /// Parse a stream of tokens, ignoring any output, and returning any errors encountered along the way.
@@ -353,7 +542,7 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         let mut inp = own.as_ref_start();
         let res = self.then_ignore(end()).go::<Check>(&mut inp);
         let alt = inp.errors.alt.take();
-        let mut errs = own.into_errs();
+        let (mut errs, warns) = own.into_errs_and_warnings();
         let out = match res {
             Ok(()) => Some(()),
             Err(()) => {
@@ -361,7 +550,43 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
                 None
             }
         };
-        ParseResult::new(out, errs)
+        ParseResult::new(out, errs, warns)
+    }
+
+    /// Analyse the grammar of this parser, looking for problems that would otherwise only show up at runtime (if at
+    /// all).
+    ///
+    /// The following problems are detected:
+    ///
+    /// - Repeating patterns (such as [`Parser::repeated`]) with items that can succeed without consuming input
+    ///   ([`Problem::NullableRepetition`])
+    /// - Alternatives of [`choice`] or [`Parser::or`] that can never match because an earlier alternative always
+    ///   matches first ([`Problem::ShadowedAlternative`])
+    /// - [`Recursive`] parsers that can recurse into themselves without consuming input ([`Problem::LeftRecursion`])
+    ///
+    /// The analysis is conservative: parsers that cannot be inspected (such as [`custom`] parsers, or parsers created
+    /// with [`Parser::filter`]) are assumed to be well-behaved, so finding no problems does not guarantee that the
+    /// grammar is free of them. Analysis does not parse anything, so it's a good idea to call this from a test.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, analysis::Problem};
+    /// let keyword = choice::<_>((just::<_, &str, extra::Default>("let"), just("letter")));
+    ///
+    /// assert!(matches!(
+    ///     Parser::<&str, _>::analyze(&keyword).as_slice(),
+    ///     [Problem::ShadowedAlternative { index: 1, .. }],
+    /// ));
+    /// ```
+    fn analyze(&self) -> Vec<Problem>
+    where
+        Self: Sized,
+        I::Token: Clone + PartialEq,
+    {
+        let mut cx = Analyzer::new();
+        self.summarize(&mut cx);
+        cx.into_problems()
     }
 
     /// Map from a slice of the input based on the current parser's span to a value.
@@ -531,6 +756,58 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Map the output of this parser to another value, making use of information about any error recovery (see
+    /// [`Parser::recover_with`]) that took place while parsing it.
+    ///
+    /// Outputs produced by a recovery strategy are otherwise indistinguishable from outputs produced by a successful
+    /// parse. This allows later passes, such as type checking, to avoid reporting cascading errors against nodes that
+    /// were only partially parsed. See [`RecoveryInfo`] for the information available.
+    ///
+    /// The output type of this parser is `U`, the same as the function's output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Debug, PartialEq)]
+    /// enum Expr<'a> {
+    ///     Error,
+    ///     Ident(&'a str),
+    ///     Call(&'a str, Vec<Expr<'a>>, bool),
+    /// }
+    ///
+    /// let ident = text::ident::<_, _, extra::Err<Rich<char>>>().padded();
+    /// let arg = ident
+    ///     .map(Expr::Ident)
+    ///     .recover_with(via_parser(none_of(",)").repeated().at_least(1).map(|_| Expr::Error)));
+    /// let call = ident
+    ///     .then(arg.separated_by(just(',')).collect().delimited_by(just('('), just(')')))
+    ///     // Remember whether any argument had to be recovered, so we don't type-check this call later
+    ///     .map_with_recovery(|(f, args), info| Expr::Call(f, args, info.recovered()));
+    ///
+    /// assert_eq!(
+    ///     call.parse("f(x, y)").into_result(),
+    ///     Ok(Expr::Call("f", vec![Expr::Ident("x"), Expr::Ident("y")], false)),
+    /// );
+    ///
+    /// let (out, errs) = call.parse("f(x, 42)").into_output_errors();
+    /// assert_eq!(out, Some(Expr::Call("f", vec![Expr::Ident("x"), Expr::Error], true)));
+    /// assert_eq!(errs.len(), 1);
+    /// ```
+    fn map_with_recovery<U, F: Fn(O, RecoveryInfo<I::Span>) -> U>(
+        self,
+        f: F,
+    ) -> MapWithRecovery<Self, O, F>
+    where
+        Self: Sized,
+    {
+        MapWithRecovery {
+            parser: self,
+            mapper: f,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// After a successful parse, apply a fallible function to the output. If the function produces an error, treat it
     /// as a parsing error.
     ///
@@ -684,6 +961,50 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Give this parser a name.
+    ///
+    /// Names have no effect on the behaviour of the parser. Instead, they identify the parser in diagnostic tooling:
+    ///
+    /// - The [`Debug`](fmt::Debug) output of combinators shows the shape of the grammar, with named parsers appearing
+    ///   by their name. This makes it possible to debug parsers returned as `impl Parser` (which have no `Debug` impl
+    ///   of their own), and keeps the output of large grammars readable.
+    ///
+    /// - Parsers that erase or hide their inner parser, such as those produced by [`Parser::boxed`] and
+    ///   [`recursive()`], show the name of the parser they contain (if it has one) in their `Debug` output.
+    ///
+    /// - When the `profile` feature is enabled, named parsers record statistics about their invocations (see the
+    ///   `profile` module).
+    ///
+    /// To refer to a parser by name in errors, see `Parser::labelled`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// let ident = text::ident::<&str, _, extra::Default>().named("ident");
+    /// let assign = just("let")
+    ///     .padded()
+    ///     .ignore_then(ident)
+    ///     .then_ignore(just('=').padded())
+    ///     .then(text::int(10).named("int"));
+    ///
+    /// assert_eq!(ident.name(), "ident");
+    /// assert_eq!(assign.parse("let x = 42").into_result(), Ok(("x", "42")));
+    /// assert_eq!(
+    ///     format!("{:?}", assign),
+    ///     r#"Then(ThenIgnore(IgnoreThen(Padded(Just("let")), ident), Padded(Just('='))), int)"#,
+    /// );
+    ///
+    /// // Boxing a named parser preserves its name
+    /// assert_eq!(format!("{:?}", assign.named("assign").boxed()), "Boxed(assign)");
+    /// ```
+    fn named(self, name: &'static str) -> Named<Self>
+    where
+        Self: Sized,
+    {
+        Named { parser: self, name }
+    }
+
     /// Parse one thing and then another thing, yielding a tuple of the two outputs.
     ///
     /// The output type of this parser is `(O, U)`, a combination of the outputs of both parsers.
@@ -844,6 +1165,85 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
         }
     }
 
+    /// Parse the output of another parser with this parser, where the output is an input of a different type and the
+    /// span of the outer input that it corresponds to.
+    ///
+    /// This parser runs on the nested input wrapped in [`Rebased`](input::Rebased) (see [`Input::rebase`]), so its
+    /// spans point into the outer input rather than the nested one. For example, the contents of a string literal
+    /// token can be parsed with character-level parsers, while errors still refer to the right place in the original
+    /// file. Errors produced by this parser are converted into errors of the outer parser using `map_err`.
+    ///
+    /// The nested input must be parsed in its entirety. If this parser fails, so does the resulting parser. The nested
+    /// input is parsed separately from the outer input, so any [`ParseOptions`] that the outer parse was given do not
+    /// apply to it. Likewise, this parser does not share the outer parser's state or context: it starts from the
+    /// default [`State`](extra::ParserExtra::State) and [`Context`](extra::ParserExtra::Context) of its own extra
+    /// type each time the nested input is parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, error::Rich};
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Token<'a> {
+    ///     Ident(&'a str),
+    ///     Eq,
+    ///     // The contents of a string literal, without its quotes
+    ///     Str(&'a str),
+    /// }
+    ///
+    /// // x = "a\nb"
+    /// let tokens = [
+    ///     (Token::Ident("x"), SimpleSpan::from(0..1)),
+    ///     (Token::Eq, SimpleSpan::from(2..3)),
+    ///     (Token::Str("a\\nb"), SimpleSpan::from(4..10)),
+    /// ];
+    ///
+    /// let escape = just('\\').ignore_then(just('n').to('\n'));
+    /// let string = none_of::<_, _, extra::Err<Rich<char>>>('\\')
+    ///     .or(escape)
+    ///     .repeated()
+    ///     .collect::<String>()
+    ///     .nested_in_with(
+    ///         select! { Token::Str(s) => s }
+    ///             .map_with_span(|s, span: SimpleSpan| (s, SimpleSpan::from(span.start + 1..span.end - 1))),
+    ///         |err: Rich<char>| Rich::custom(*err.span(), err),
+    ///     );
+    ///
+    /// let assign = select! { Token::Ident(x) => x }
+    ///     .then_ignore(just::<_, _, extra::Err<Rich<_>>>(Token::Eq))
+    ///     .then(string);
+    ///
+    /// let input = tokens.spanned(SimpleSpan::from(10..10));
+    /// assert_eq!(
+    ///     assign.parse(input).into_result(),
+    ///     Ok(("x", "a\nb".to_string())),
+    /// );
+    ///
+    /// // x = "a\qb"
+    /// let tokens = [
+    ///     (Token::Ident("x"), SimpleSpan::from(0..1)),
+    ///     (Token::Eq, SimpleSpan::from(2..3)),
+    ///     (Token::Str("a\\qb"), SimpleSpan::from(4..10)),
+    /// ];
+    ///
+    /// // The invalid escape is reported at its position in the original source
+    /// let input = tokens.spanned(SimpleSpan::from(10..10));
+    /// let errs = assign.parse(input).into_errors();
+    /// assert_eq!(errs.len(), 1);
+    /// assert_eq!(errs[0].span(), &SimpleSpan::from(7..8));
+    /// ```
+    fn nested_in_with<B, F>(self, other: B, map_err: F) -> NestedInWith<Self, B, F, I, E>
+    where
+        Self: Sized,
+    {
+        NestedInWith {
+            parser_a: self,
+            parser_b: other,
+            map_err,
+            phantom: EmptyPhantom::new(),
+        }
+    }
+
     /// Parse one thing and then another thing, creating the second parser from the result of
     /// the first. If you only have a couple cases to handle, prefer [`Parser::or`].
     ///
@@ -999,6 +1399,7 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
             parser: self,
             start,
             end,
+            recover_unclosed: false,
             phantom: EmptyPhantom::new(),
         }
     }
@@ -1196,7 +1597,7 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// Parse a pattern, separated by another, any number of times.
     ///
     /// You can use [`SeparatedBy::allow_leading`] or [`SeparatedBy::allow_trailing`] to allow leading or trailing
-    /// separators.
+    /// separators, and [`SeparatedBy::recover_until`] to recover from errors within the list.
     ///
     /// The output type of this parser can be any [`Container`].
     ///
@@ -1227,6 +1628,8 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
             at_most: !0,
             allow_leading: false,
             allow_trailing: false,
+            recover: false,
+            closer: end(),
             #[cfg(debug_assertions)]
             location: *Location::caller(),
             phantom: EmptyPhantom::new(),
@@ -1528,6 +1931,9 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     /// If you wish parsing of this pattern to halt when an error is generated instead of continuing, consider using
     /// [`Parser::try_map`] instead.
     ///
+    /// Non-fatal findings that should not count as errors (deprecated syntax, redundant parentheses, etc.) can be
+    /// reported with [`Emitter::warn`] instead of [`Emitter::emit`]. See [`ParseResult::warnings`].
+    ///
     /// The output type of this parser is `U`, the result of the validation closure.
     ///
     /// # Examples
@@ -1544,6 +1950,18 @@ pub trait Parser<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>:
     ///
     /// assert_eq!(large_int.parse("537").into_result(), Ok(537));
     /// assert!(large_int.parse("243").into_result().is_err());
+    ///
+    /// let padded_int = text::digits::<_, _, extra::Err<Rich<char>>>(10)
+    ///     .slice()
+    ///     .validate(|x: &str, span, emitter| {
+    ///         if x.len() > 1 && x.starts_with('0') { emitter.warn(Rich::custom(span, "redundant leading zeroes")) }
+    ///         x
+    ///     });
+    ///
+    /// // Warnings don't prevent parsing from succeeding
+    /// let res = padded_int.parse("007");
+    /// assert_eq!(res.warnings().len(), 1);
+    /// assert_eq!(res.into_result(), Ok("007"));
     /// ```
     fn validate<U, F>(self, f: F) -> Validate<Self, O, F>
     where
@@ -1954,6 +2372,7 @@ where
                 phantom: EmptyPhantom::new(),
             }),
             Vec::new(),
+            Vec::new(),
         )
     }
 
@@ -1980,6 +2399,7 @@ where
                 phantom: EmptyPhantom::new(),
             }),
             Vec::new(),
+            Vec::new(),
         )
     }
 }
@@ -2038,6 +2458,19 @@ impl<'a, 'b, I: Input<'a>, O, E: ParserExtra<'a, I>> Clone for Boxed<'a, 'b, I,
     }
 }
 
+impl<'a, 'b, I: Input<'a>, O, E: ParserExtra<'a, I>> fmt::Debug for Boxed<'a, 'b, I, O, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The inner parser's type is erased, so the best we can do is to show its name
+        match self.inner.name() {
+            Some(name) => f
+                .debug_tuple("Boxed")
+                .field(&format_args!("{}", name))
+                .finish(),
+            None => f.debug_tuple("Boxed").field(&format_args!("..")).finish(),
+        }
+    }
+}
+
 impl<'a, 'b, I, O, E> ParserSealed<'a, I, O, E> for Boxed<'a, 'b, I, O, E>
 where
     I: Input<'a>,
@@ -2047,6 +2480,37 @@ where
         M::invoke(&*self.inner, inp)
     }
 
+    fn name(&self) -> Option<&'static str> {
+        self.inner.name()
+    }
+
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.inner.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.inner.generate(gen)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate_where(
+        &self,
+        gen: &mut Generator<'_, I::Token>,
+        f: &dyn Fn(&O) -> bool,
+    ) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.inner.generate_where(gen, f)
+    }
+
     fn boxed<'c>(self) -> Boxed<'a, 'c, I, O, E>
     where
         Self: MaybeSync + Sized + 'a + 'c,
@@ -2163,6 +2627,25 @@ where
 ///     ])),
 /// );
 /// ```
+///
+/// # Labels
+///
+/// When no pattern matches, the error produced by `select!` does not expect anything in particular. With the `label`
+/// feature enabled, each arm may be prefixed with a label that describes the token it accepts, such that errors
+/// expect those labels instead (this requires that the error type implements `LabelError` for the label type):
+///
+/// ```ignore
+/// select! {
+///     #[label("identifier")] Token::Ident(s) => Expr::Var(s),
+///     #[label("number")] Token::Num(x) => Expr::Num(x),
+/// }
+/// ```
+///
+/// With [`Rich`](error::Rich) errors, this yields messages such as "found '+' expected identifier, or number".
+///
+/// A bare `#[label]` derives a [`&str`] label from the last segment of the arm's pattern path (`Ident` for
+/// `Token::Ident(s)`). Arms without a label don't contribute to the expected patterns. See also
+/// `Select::with_labels`.
 #[macro_export]
 macro_rules! select {
     ($($p:pat $(= $span:ident)? $(if $guard:expr)? $(=> $out:expr)?),+ $(,)?) => ({
@@ -2173,6 +2656,15 @@ macro_rules! select {
             }
         )
     });
+    ($($(#[label $(($label:expr))?])? $p:pat $(= $span:ident)? $(if $guard:expr)? $(=> $out:expr)?),+ $(,)?) => ({
+        $crate::primitive::select(
+            move |x, span| match x {
+                $($p $(if $guard)? => ::core::option::Option::Some({ $(let $span = span;)? () $(;$out)? })),+,
+                _ => ::core::option::Option::None,
+            }
+        )
+        .with_labels([$($($crate::__select_label!($p $(, $label)?),)?)+])
+    });
 }
 
 /// A version of [`select!`] that selects on token by reference instead of by value.
@@ -2180,6 +2672,8 @@ macro_rules! select {
 /// Useful if you want to extract elements from a token in a zero-copy manner.
 ///
 /// `select_ref` requires that the parser input implements [`BorrowInput`].
+///
+/// Arms may be labelled in the same way as with [`select!`].
 #[macro_export]
 macro_rules! select_ref {
     ($($p:pat $(= $span:ident)? $(if $guard:expr)? $(=> $out:expr)?),+ $(,)?) => ({
@@ -2190,6 +2684,26 @@ macro_rules! select_ref {
             }
         )
     });
+    ($($(#[label $(($label:expr))?])? $p:pat $(= $span:ident)? $(if $guard:expr)? $(=> $out:expr)?),+ $(,)?) => ({
+        $crate::primitive::select_ref(
+            move |x, span| match x {
+                $($p $(if $guard)? => ::core::option::Option::Some({ $(let $span = span;)? () $(;$out)? })),+,
+                _ => ::core::option::Option::None,
+            }
+        )
+        .with_labels([$($($crate::__select_label!($p $(, $label)?),)?)+])
+    });
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __select_label {
+    ($p:pat) => {
+        $crate::primitive::pattern_label(::core::stringify!($p))
+    };
+    ($p:pat, $label:expr) => {
+        $label
+    };
 }
 
 #[cfg(test)]
@@ -2522,6 +3036,94 @@ mod tests {
         // TODO what about IterConfigure and TryIterConfigure?
     }
 
+    #[test]
+    fn warnings() {
+        use self::prelude::*;
+
+        fn parser<'a>() -> impl Parser<'a, &'a str, Vec<&'a str>, extra::Err<Rich<'a, char>>> {
+            let parens = text::int(10)
+                .delimited_by(just('('), just(')'))
+                .validate(|x, span, emitter| {
+                    emitter.warn(Rich::custom(span, "redundant parentheses"));
+                    x
+                });
+            // The first branch warns, but is backtracked out of: its warning must not survive
+            let list = parens
+                .then_ignore(just(';'))
+                .or(text::int(10).delimited_by(just('('), just(')')));
+
+            list.or(text::int(10))
+                .padded()
+                .separated_by(just(','))
+                .collect()
+        }
+
+        let res = parser().parse("1, (2);, 3");
+        assert!(!res.has_errors());
+        assert_eq!(res.warnings().len(), 1);
+        assert_eq!(
+            res.diagnostics().map(|(s, _)| s).collect::<Vec<_>>(),
+            vec![Severity::Warning],
+        );
+        assert_eq!(res.into_result(), Ok(vec!["1", "2", "3"]));
+
+        let res = parser().parse("1, (2), 3");
+        assert!(!res.has_errors());
+        assert!(!res.has_warnings());
+    }
+
+    #[test]
+    fn parse_options_limits() {
+        use self::prelude::*;
+
+        fn parser<'a>() -> impl Parser<'a, &'a str, Vec<char>, extra::Err<Rich<'a, char>>> {
+            let item = one_of("abc")
+                .recover_with(via_parser(any().map(|_| '?')))
+                .padded();
+            item.repeated().collect()
+        }
+
+        let src = "a x b y c z";
+
+        let res = parser().parse(src);
+        assert_eq!(res.errors().len(), 3);
+        assert_eq!(res.output(), Some(&vec!['a', '?', 'b', '?', 'c', '?']));
+
+        let res = parser().parse_with_options(src, ParseOptions::new().max_errors(3));
+        assert_eq!(res.errors().len(), 3);
+        assert!(res.has_output());
+
+        let res = parser().parse_with_options(src, ParseOptions::new().max_errors(1));
+        assert!(!res.has_output());
+        let errs = res.into_errors();
+        assert_eq!(errs.len(), 2);
+        assert_eq!(
+            errs[1],
+            Rich::custom((6..6).into(), "too many errors, parsing halted")
+        );
+
+        // Refusing to recover doesn't halt parsing if the parser can still succeed without recovery
+        let res = parser().parse_with_options("a b", ParseOptions::new().max_recoveries(0));
+        assert_eq!(res.into_result(), Ok(vec!['a', 'b']));
+        let res = parser().parse_with_options(src, ParseOptions::new().max_recoveries(2));
+        assert_eq!(res.errors().len(), 3);
+        assert!(!res.has_output());
+
+        // A recovery refused within a branch that is backtracked out of doesn't halt parsing
+        let branch = just::<_, _, extra::Err<Rich<char>>>('a')
+            .recover_with(via_parser(just('z')))
+            .then(just('!'))
+            .ignored()
+            .or(just("bq").ignored())
+            .then(just(';'));
+        let errs = branch
+            .parse_with_options("b?", ParseOptions::new().max_recoveries(0))
+            .into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), Some(&'?'));
+        assert_eq!(errs[0].span(), &SimpleSpan::from(1..2));
+    }
+
     #[test]
     #[should_panic]
     fn recursive_define_twice() {
@@ -2551,4 +3153,408 @@ mod tests {
         let expr = todo::<&str, String, extra::Default>();
         expr.then_ignore(end()).parse("a+b+c");
     }
+
+    #[test]
+    fn dispatched_choice() {
+        use self::prelude::*;
+
+        fn stmts<'a, P: Parser<'a, &'a str, u32, extra::Err<Rich<'a, char>>>>(
+            stmt: P,
+        ) -> impl Parser<'a, &'a str, Vec<u32>, extra::Err<Rich<'a, char>>> {
+            stmt.padded().repeated().collect()
+        }
+
+        macro_rules! alts {
+            () => {
+                (
+                    text::keyword("let").to(0),
+                    text::keyword("letter").to(1),
+                    just("+=").to(2),
+                    one_of("+-").to(3),
+                    text::int(10).to(4),
+                    just('(').or_not().ignore_then(just('x')).to(5),
+                    any().filter(|c: &char| c.is_uppercase()).to(6),
+                    just('?').rewind().ignore_then(just("?!")).to(7),
+                )
+            };
+        }
+
+        let inputs = [
+            "let letter + += - 42 (x x Q",
+            "let let 12",
+            "letx",
+            "(",
+            "(y",
+            "let ?! ?",
+            "let ; +",
+            "",
+        ];
+        for input in inputs {
+            let expected = stmts(choice(alts!())).parse(input).into_output_errors();
+            assert_eq!(
+                stmts(choice(alts!()).dispatched())
+                    .parse(input)
+                    .into_output_errors(),
+                expected,
+                "{:?}",
+                input,
+            );
+            assert_eq!(
+                stmts(
+                    choice(["a", "b", "ab", "b"].map(|s| just(s).to(s.len() as u32))).dispatched()
+                )
+                .parse(input)
+                .into_output_errors(),
+                stmts(choice(
+                    ["a", "b", "ab", "b"].map(|s| just(s).to(s.len() as u32))
+                ))
+                .parse(input)
+                .into_output_errors(),
+                "{:?}",
+                input,
+            );
+        }
+
+        // Skipped alternatives still contribute to the errors of later parsers
+        let ops = || {
+            choice((
+                just::<_, &str, extra::Err<Rich<char>>>('x'),
+                just('+'),
+                just("+=").to('='),
+            ))
+        };
+        for input in ["+", "+=", "x"] {
+            assert_eq!(
+                ops()
+                    .dispatched()
+                    .then(just(';'))
+                    .parse(input)
+                    .into_errors(),
+                ops().then(just(';')).parse(input).into_errors(),
+                "{:?}",
+                input,
+            );
+        }
+
+        // Alternatives that can't match the next token aren't tried at all
+        let tries = |dispatch: bool| {
+            let counted = |c| {
+                empty::<&str, extra::Full<Rich<char>, u32, ()>>()
+                    .map_with_state(|_, _, tries: &mut u32| *tries += 1)
+                    .then(just(c))
+            };
+            let alts = choice((counted('a'), counted('b'), counted('c')));
+            let mut tries = 0;
+            if dispatch {
+                alts.dispatched().parse_with_state("c", &mut tries)
+            } else {
+                alts.parse_with_state("c", &mut tries)
+            };
+            tries
+        };
+        assert_eq!(tries(false), 3);
+        assert_eq!(tries(true), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "label")]
+    fn dispatched_choice_labels() {
+        use self::prelude::*;
+
+        // Labelled alternatives report their label rather than the tokens they begin with, so are always tried
+        let stmt = || {
+            choice((
+                just::<_, &str, extra::Err<Rich<char, SimpleSpan, &str>>>("let")
+                    .ignored()
+                    .labelled("let statement"),
+                text::int(10).ignored().labelled("number"),
+                just('x').ignored(),
+            ))
+        };
+        for input in ["x", "l", "let", "1", ""] {
+            assert_eq!(
+                stmt()
+                    .dispatched()
+                    .then(just(';'))
+                    .parse(input)
+                    .into_errors(),
+                stmt().then(just(';')).parse(input).into_errors(),
+                "{:?}",
+                input,
+            );
+        }
+        assert_eq!(
+            stmt()
+                .dispatched()
+                .then(just(';'))
+                .parse("y")
+                .into_errors()
+                .into_iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>(),
+            vec!["found 'y' expected let statement, number, or 'x'"],
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "label")]
+    fn select_labels() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Token {
+            Ident(&'static str),
+            Num(u64),
+            Plus,
+        }
+
+        impl fmt::Display for Token {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{:?}", self)
+            }
+        }
+
+        type Input<'a> = &'a [Token];
+        type Extra<'a> = extra::Err<Rich<'a, Token>>;
+
+        let atom: primitive::Select<_, Input, _, Extra, _> = select! {
+            #[label("identifier")] Token::Ident(s) => s.len() as u64,
+            #[label("number")] Token::Num(x) if x < 100 => x,
+        };
+        assert_eq!(atom.parse(&[Token::Num(42)] as &[_]).into_result(), Ok(42));
+        assert_eq!(
+            atom.parse(&[Token::Ident("abc")] as &[_]).into_result(),
+            Ok(3)
+        );
+        let errs = atom.parse(&[Token::Plus] as &[_]).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(
+            errs[0].to_string(),
+            "found 'Plus' expected identifier, or number"
+        );
+
+        // Labels are merged with the expected patterns of other alternatives
+        let expr = atom.or(just(Token::Plus).to(0));
+        let errs = expr.parse(&[Token::Num(100)] as &[_]).into_errors();
+        assert_eq!(
+            errs[0].to_string(),
+            "found 'Num(100)' expected identifier, number, or 'Plus'"
+        );
+
+        // Derived labels, with `select_ref!`
+        let atom: primitive::SelectRef<_, Input, _, Extra, _> = select_ref! {
+            #[label] Token::Ident(s) => *s,
+            Token::Num(_) => "num",
+        };
+        let errs = atom.parse(&[Token::Plus] as &[_]).into_errors();
+        assert_eq!(errs[0].to_string(), "found 'Plus' expected Ident");
+        // Errors at the end of input expect the labels too
+        let errs = atom.parse(&[] as &[_]).into_errors();
+        assert_eq!(errs[0].to_string(), "found end of input expected Ident");
+    }
+
+    #[test]
+    fn text_stream() {
+        use self::input::Stream;
+
+        let assign = text::keyword::<_, _, _, extra::Default>("let")
+            .ignore_then(text::ident().padded())
+            .then_ignore(just('='))
+            .then(text::int(10).padded());
+        assert_eq!(
+            assign
+                .parse(Stream::from_iter("let x1 = 42".chars()))
+                .into_result(),
+            Ok((String::from("x1"), String::from("42"))),
+        );
+        assert!(assign
+            .parse(Stream::from_iter("letx = 1".chars()))
+            .has_errors());
+
+        // Inputs with context still slice their underlying input
+        let ident = text::ident::<_, _, extra::Default>();
+        assert_eq!(ident.parse("foo".with_context(())).into_result(), Ok("foo"));
+    }
+
+    #[test]
+    fn skip_tokens() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Token {
+            Num(u64),
+            Plus,
+            Space,
+        }
+
+        // Whitespace is kept in the token vector, but not seen by the parser
+        let src = " 1 +  + 2 ";
+        let tokens = src
+            .char_indices()
+            .map(|(i, c)| {
+                let tok = match c {
+                    ' ' => Token::Space,
+                    '+' => Token::Plus,
+                    c => Token::Num(c.to_digit(10).unwrap() as u64),
+                };
+                (tok, SimpleSpan::from(i..i + 1))
+            })
+            .collect::<Vec<_>>();
+        fn is_space(tok: &Token) -> bool {
+            *tok == Token::Space
+        }
+        let input = |end: usize| {
+            tokens[..end]
+                .spanned(SimpleSpan::from(end..end))
+                .skip_tokens(is_space)
+        };
+
+        let num = select! { Token::Num(x) => x };
+        let sum = num
+            .separated_by(just::<_, _, extra::Err<Rich<_>>>(Token::Plus))
+            .collect::<Vec<_>>()
+            .map_with_span(|nums, span: SimpleSpan| (nums, span));
+
+        let errs = sum.parse(input(src.len())).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), Some(&Token::Plus));
+        assert_eq!(errs[0].span(), &SimpleSpan::from(6..7));
+
+        // Skipped tokens at the end of the input are skipped too
+        let errs = sum.parse(input(5)).into_errors();
+        assert_eq!(errs[0].found(), None);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(5..5));
+
+        // Leading skipped tokens are not part of spans
+        assert_eq!(
+            sum.parse(input(3)).into_result(),
+            Ok((vec![1], SimpleSpan::from(1..2)))
+        );
+    }
+
+    #[test]
+    fn nested_in_with() {
+        use self::input::WithContext;
+
+        type Span<'a> = (&'a str, SimpleSpan);
+
+        // The contents of a string, along with its span
+        fn string<'a>() -> impl Parser<
+            'a,
+            WithContext<&'a str, &'a str>,
+            (&'a str, Span<'a>),
+            extra::Err<Rich<'a, char, Span<'a>>>,
+        > + Clone {
+            just("say ").ignore_then(
+                none_of('"')
+                    .repeated()
+                    .slice()
+                    .map_with_span(|s, span| (s, span))
+                    .delimited_by(just('"'), just('"')),
+            )
+        }
+
+        let letters = any::<_, extra::Err<Rich<char, Span>>>()
+            .validate(|c: char, span, emitter| {
+                if c.is_ascii_digit() {
+                    emitter.emit(Rich::custom(span, "digit"))
+                }
+                c
+            })
+            .repeated()
+            .collect::<String>()
+            .nested_in_with(string(), |err| err);
+
+        // Secondary errors are translated into the outer input
+        let (out, errs) = letters
+            .parse(r#"say "ab1c2""#.with_context("main"))
+            .into_output_errors();
+        assert_eq!(out.as_deref(), Some("ab1c2"));
+        assert_eq!(
+            errs.iter().map(|err| err.span()).collect::<Vec<_>>(),
+            [
+                &("main", SimpleSpan::from(7..8)),
+                &("main", SimpleSpan::from(9..10))
+            ],
+        );
+
+        // Checking doesn't produce the nested output
+        let outputs = core::cell::Cell::new(0);
+        let counted = any::<_, extra::Err<Rich<char, Span>>>()
+            .repeated()
+            .collect::<String>()
+            .map(|s| {
+                outputs.set(outputs.get() + 1);
+                s
+            })
+            .nested_in_with(string(), |err| err);
+        let input = r#"say "abc""#.with_context("main");
+        assert!(!counted.check(input).has_errors());
+        assert_eq!(outputs.get(), 0);
+        assert_eq!(counted.parse(input).into_result().as_deref(), Ok("abc"));
+        assert_eq!(outputs.get(), 1);
+
+        // As is the error that causes the nested parser to fail
+        let only_a = just::<_, _, extra::Err<Rich<char, Span>>>('a')
+            .repeated()
+            .collect::<String>()
+            .nested_in_with(string(), |err| err);
+        let errs = only_a
+            .parse(r#"say "aab""#.with_context("main"))
+            .into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), Some(&'b'));
+        assert_eq!(errs[0].span(), &("main", SimpleSpan::from(7..8)));
+
+        // Alternatives that got further through the outer input are still reported
+        let errs = just(r#"say "aab"xx!"#)
+            .to(String::new())
+            .or(only_a.then_ignore(just("xx")))
+            .parse(r#"say "aab"xx"#.with_context("main"))
+            .into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].found(), None);
+        assert_eq!(errs[0].span(), &("main", SimpleSpan::from(11..11)));
+        assert_eq!(
+            errs[0].expected().collect::<Vec<_>>(),
+            [&error::RichPattern::Token('!'.into())],
+        );
+    }
+
+    #[test]
+    fn debug_names() {
+        let expr = recursive::<&str, _, extra::Default, _, _>(|expr| {
+            let atom = choice((
+                text::int(10).named("int"),
+                expr.delimited_by(just('('), just(')')),
+            ));
+            atom.clone()
+                .foldl(just('+').ignore_then(atom).repeated(), |a, _| a)
+                .named("expr")
+        });
+        assert_eq!(format!("{:?}", expr), "Recursive(expr)");
+
+        let list = expr
+            .clone()
+            .separated_by(just(','))
+            .collect::<Vec<_>>()
+            .or_not();
+        assert_eq!(
+            format!("{:?}", list),
+            "OrNot(Collect(SeparatedBy(Recursive(expr), Just(','))))"
+        );
+        assert_eq!(format!("{:?}", Parser::boxed(list)), "Boxed(..)");
+
+        let unnamed = recursive::<&str, &str, extra::Default, _, _>(|_| text::int(10));
+        assert_eq!(format!("{:?}", unnamed), "Recursive(..)");
+
+        let mut decl = Recursive::<recursive::Indirect<&str, &str, extra::Default>>::declare();
+        assert_eq!(format!("{:?}", decl), "Recursive(..)");
+        decl.define(text::int(10).named("int"));
+        assert_eq!(format!("{:?}", decl), "Recursive(int)");
+
+        let recovering = text::int::<&str, _, extra::Default>(10)
+            .named("int")
+            .recover_with(skip_then_retry_until(any().ignored(), end()));
+        assert_eq!(
+            format!("{:?}", recovering),
+            "RecoverWith(int, SkipThenRetryUntil(Ignored(Any), End))"
+        );
+    }
 }
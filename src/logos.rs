@@ -0,0 +1,282 @@
+//! An input adapter for lexers generated by [`logos`](https://docs.rs/logos/0.12).
+//!
+//! [`LogosInput`] runs a lexer to completion and provides its tokens, along with their spans in the source, as an
+//! input for token-level parsers. Error tokens produced by the lexer (i.e: `Logos::ERROR`)
+//! are not passed to the parser. Instead, each is reported as an error at its span, and parsing continues with the
+//! remaining tokens.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, logos::LogosInput};
+//! use logos::Logos;
+//!
+//! #[derive(Logos, Clone, Debug, PartialEq)]
+//! enum Token<'a> {
+//!     #[regex("[0-9]+")]
+//!     Num(&'a str),
+//!     #[token("+")]
+//!     Plus,
+//!     #[regex(r"[ \t\n]+", logos::skip)]
+//!     #[error]
+//!     Error,
+//! }
+//!
+//! let sum = select! { Token::Num(x) => x.parse::<u64>().unwrap() }
+//!     .separated_by(just::<_, _, extra::Err<Rich<_>>>(Token::Plus))
+//!     .collect::<Vec<_>>()
+//!     .map(|xs| xs.into_iter().sum::<u64>());
+//!
+//! let input = LogosInput::<Token>::new("1 + 2 + 3");
+//! assert_eq!(sum.parse(&input).into_result(), Ok(6));
+//!
+//! // The invalid '?' is reported, but the rest of the input is still parsed
+//! let input = LogosInput::<Token>::new("1 + 2? + 3");
+//! let (output, errs) = sum.parse(&input).into_output_errors();
+//! assert_eq!(output, Some(6));
+//! assert_eq!(errs.len(), 1);
+//! assert_eq!(errs[0].span(), &SimpleSpan::from(5..6));
+//! ```
+
+use super::*;
+
+use ::logos::{Lexer, Logos, Source};
+
+/// An input made up of the tokens produced by a logos lexer, with spans given by their byte offsets in the source.
+///
+/// Parsers take a reference to this type as their input (`&LogosInput`), in the same way as slices. Slicing the input
+/// (with [`Parser::slice`], for example) yields the source text covered by the tokens.
+///
+/// See the [module-level documentation](self).
+pub struct LogosInput<'src, T: Logos<'src>> {
+    source: &'src T::Source,
+    tokens: Vec<(T, SimpleSpan)>,
+    // Error tokens, along with the index of the token that follows them
+    errors: Vec<(usize, SimpleSpan)>,
+}
+
+impl<'src, T> LogosInput<'src, T>
+where
+    T: Logos<'src> + PartialEq,
+{
+    /// Lex the given source with the default extras.
+    pub fn new(source: &'src T::Source) -> Self
+    where
+        T::Extras: Default,
+    {
+        Self::from_lexer(T::lexer(source))
+    }
+
+    /// Run the given lexer to completion, collecting its tokens.
+    pub fn from_lexer(lexer: Lexer<'src, T>) -> Self {
+        let source = lexer.source();
+        let mut tokens = Vec::new();
+        let mut errors = Vec::new();
+        for (tok, span) in lexer.spanned() {
+            if tok == T::ERROR {
+                errors.push((tokens.len(), span.into()));
+            } else {
+                tokens.push((tok, span.into()));
+            }
+        }
+        Self {
+            source,
+            tokens,
+            errors,
+        }
+    }
+
+    /// Get the source that was lexed.
+    pub fn source(&self) -> &'src T::Source {
+        self.source
+    }
+
+    /// Get the tokens produced by the lexer, excluding error tokens, along with their spans.
+    pub fn tokens(&self) -> &[(T, SimpleSpan)] {
+        &self.tokens
+    }
+
+    /// Get the spans of the error tokens produced by the lexer.
+    pub fn error_spans(&self) -> impl ExactSizeIterator<Item = SimpleSpan> + '_ {
+        self.errors.iter().map(|(_, span)| *span)
+    }
+
+    fn eoi(&self) -> usize {
+        self.source.len()
+    }
+}
+
+impl<'src, T: Logos<'src>> Sealed for &LogosInput<'src, T> {}
+impl<'a, 'src, T> Input<'a> for &'a LogosInput<'src, T>
+where
+    T: Logos<'src> + PartialEq + Clone + 'a,
+    'src: 'a,
+{
+    type Offset = usize;
+    type Token = T;
+    type Span = SimpleSpan;
+
+    #[inline(always)]
+    fn start(&self) -> Self::Offset {
+        0
+    }
+
+    type TokenMaybe = &'a T;
+
+    #[inline(always)]
+    unsafe fn next_maybe(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::TokenMaybe>) {
+        self.next_ref(offset)
+    }
+
+    #[inline]
+    unsafe fn span(&self, range: Range<Self::Offset>) -> Self::Span {
+        let start = self
+            .tokens
+            .get(range.start)
+            .map_or(self.eoi(), |(_, span)| span.start);
+        let end = if range.end > range.start {
+            self.tokens
+                .get(range.end - 1)
+                .map_or(self.eoi(), |(_, span)| span.end)
+        } else {
+            start
+        };
+        (start..end).into()
+    }
+
+    #[inline(always)]
+    fn prev(offs: Self::Offset) -> Self::Offset {
+        offs.saturating_sub(1)
+    }
+
+    fn invalid_tokens(&self) -> Vec<(Self::Offset, Self::Token, Self::Span)> {
+        self.errors
+            .iter()
+            .map(|(at, span)| (*at, T::ERROR, *span))
+            .collect()
+    }
+}
+
+impl<'a, 'src, T> ExactSizeInput<'a> for &'a LogosInput<'src, T>
+where
+    T: Logos<'src> + PartialEq + Clone + 'a,
+    'src: 'a,
+{
+    #[inline]
+    unsafe fn span_from(&self, range: RangeFrom<Self::Offset>) -> Self::Span {
+        let start = self
+            .tokens
+            .get(range.start)
+            .map_or(self.eoi(), |(_, span)| span.start);
+        (start..self.eoi()).into()
+    }
+}
+
+impl<'a, 'src, T> ValueInput<'a> for &'a LogosInput<'src, T>
+where
+    T: Logos<'src> + PartialEq + Clone + 'a,
+    'src: 'a,
+{
+    #[inline(always)]
+    unsafe fn next(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::Token>) {
+        let (offset, tok) = self.next_ref(offset);
+        (offset, tok.cloned())
+    }
+}
+
+impl<'a, 'src, T> BorrowInput<'a> for &'a LogosInput<'src, T>
+where
+    T: Logos<'src> + PartialEq + Clone + 'a,
+    'src: 'a,
+{
+    #[inline(always)]
+    unsafe fn next_ref(&self, offset: Self::Offset) -> (Self::Offset, Option<&'a Self::Token>) {
+        match self.tokens.get(offset) {
+            Some((tok, _)) => (offset + 1, Some(tok)),
+            None => (offset, None),
+        }
+    }
+}
+
+impl<'a, 'src, T> SliceInput<'a> for &'a LogosInput<'src, T>
+where
+    T: Logos<'src> + PartialEq + Clone + 'a,
+    'src: 'a,
+{
+    type Slice = &'src <T::Source as Source>::Slice;
+
+    #[inline]
+    fn slice(&self, range: Range<Self::Offset>) -> Self::Slice {
+        // SAFETY: slicing doesn't rely on the offsets being valid, out-of-bounds offsets refer to the end of input
+        let span = unsafe { self.span(range) };
+        self.source
+            .slice(span.into_range())
+            .expect("token spans should lie within the source")
+    }
+
+    #[inline]
+    fn slice_from(&self, from: RangeFrom<Self::Offset>) -> Self::Slice {
+        // SAFETY: as above
+        let span = unsafe { self.span_from(from) };
+        self.source
+            .slice(span.into_range())
+            .expect("token spans should lie within the source")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Rich, prelude::*};
+
+    #[derive(Logos, Clone, Debug, PartialEq)]
+    enum Token<'a> {
+        #[regex("[a-z]+")]
+        Ident(&'a str),
+        #[token("(")]
+        LParen,
+        #[token(")")]
+        RParen,
+        #[regex(r"[ \t\n]+", ::logos::skip)]
+        #[error]
+        Error,
+    }
+
+    impl<'a> fmt::Display for Token<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{:?}", self)
+        }
+    }
+
+    fn sexpr<'a>(
+    ) -> impl Parser<'a, &'a LogosInput<'a, Token<'a>>, &'a str, extra::Err<Rich<'a, Token<'a>>>>
+    {
+        recursive(|sexpr| {
+            let ident = select! { Token::Ident(x) => x };
+            ident.or(sexpr
+                .repeated()
+                .delimited_by(just(Token::LParen), just(Token::RParen))
+                .slice())
+        })
+    }
+
+    #[test]
+    fn lexer_errors() {
+        let input = LogosInput::<Token>::new("(a (b 1) c)");
+        let result = sexpr().parse(&input);
+        // Slices are of the source, not the tokens
+        assert_eq!(result.output(), Some(&"(a (b 1) c)"));
+        let errs = result.into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(6..7));
+        assert_eq!(errs[0].found(), Some(&Token::Error));
+
+        // Errors from the lexer are reported alongside those from the parser, and end of input has the right span
+        let input = LogosInput::<Token>::new("(a 2 ");
+        let errs = sexpr().parse(&input).into_errors();
+        assert_eq!(errs.len(), 2);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(3..4));
+        assert_eq!(errs[1].span(), &SimpleSpan::from(5..5));
+        assert_eq!(errs[1].found(), None);
+    }
+}
@@ -0,0 +1,311 @@
+//! Conversion of parser errors into [Language Server Protocol](https://microsoft.github.io/language-server-protocol/)
+//! diagnostics.
+//!
+//! This module does not depend on any LSP implementation: it only provides plain data types that mirror the shape of
+//! the protocol's `Position`, `Range` and `Diagnostic` structures, along with the (surprisingly fiddly) logic required
+//! to turn the byte offsets used by `&str` inputs into the UTF-16 line/character pairs that the protocol expects.
+//! Converting these types into those of your LSP crate of choice should be a field-by-field copy.
+//!
+//! # Examples
+//!
+//! ```
+//! use chumsky::{prelude::*, lsp::{LineIndex, Position}};
+//!
+//! let src = "let x = 1;\nlet y = ;\n";
+//! let parser = text::keyword::<_, _, _, extra::Err<Rich<char>>>("let")
+//!     .padded()
+//!     .ignore_then(text::ident())
+//!     .then_ignore(just('=').padded())
+//!     .then(text::int(10))
+//!     .then_ignore(just(';').padded())
+//!     .repeated();
+//!
+//! let index = LineIndex::new(src);
+//! let diags = parser
+//!     .parse(src)
+//!     .errors()
+//!     .map(|err| index.diagnostic(err))
+//!     .collect::<Vec<_>>();
+//!
+//! assert_eq!(diags.len(), 1);
+//! assert_eq!(diags[0].range.start, Position { line: 1, character: 8 });
+//! ```
+
+use super::*;
+use alloc::string::ToString;
+
+/// A position within a text document, as understood by the Language Server Protocol.
+///
+/// Both `line` and `character` are zero-based. `character` is measured in UTF-16 code units, as required by the
+/// protocol's default position encoding.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    /// The zero-based line number.
+    pub line: u32,
+    /// The zero-based character offset within the line, in UTF-16 code units.
+    pub character: u32,
+}
+
+/// A range within a text document, as understood by the Language Server Protocol.
+///
+/// Like chumsky's spans, the end position is exclusive.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Range {
+    /// The start position of the range.
+    pub start: Position,
+    /// The end (exclusive) position of the range.
+    pub end: Position,
+}
+
+/// The severity of a [`Diagnostic`].
+///
+/// The discriminants match the values used by the protocol.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiagnosticSeverity {
+    /// An error that prevents the document from being valid.
+    Error = 1,
+    /// A problem that does not prevent the document from being valid.
+    Warning = 2,
+    /// An informational message.
+    Information = 3,
+    /// A hint, usually rendered subtly by editors.
+    Hint = 4,
+}
+
+impl From<Severity> for DiagnosticSeverity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Warning => DiagnosticSeverity::Warning,
+            Severity::Error => DiagnosticSeverity::Error,
+        }
+    }
+}
+
+/// Additional information attached to a [`Diagnostic`], such as the labelled context that an error occurred within.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DiagnosticRelatedInformation {
+    /// The range that this information refers to.
+    pub range: Range,
+    /// The message associated with this information.
+    pub message: String,
+}
+
+/// A diagnostic, as understood by the Language Server Protocol.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    /// The range at which the diagnostic applies.
+    pub range: Range,
+    /// The severity of the diagnostic.
+    pub severity: DiagnosticSeverity,
+    /// A human-readable description of the problem.
+    pub message: String,
+    /// Related locations, such as the labelled contexts that the error occurred within (from least general to most).
+    pub related_information: Vec<DiagnosticRelatedInformation>,
+}
+
+/// An index of the line starts of a `&str` source, used to convert byte offsets into LSP [`Position`]s.
+///
+/// Lines may be terminated by `\n`, `\r\n` or `\r`, as with the protocol itself. Offsets that lie past the end of the
+/// source are clamped to the end of the source, and offsets that lie within a multi-byte character are rounded down to
+/// the start of that character.
+#[derive(Clone, Debug)]
+pub struct LineIndex<'src> {
+    src: &'src str,
+    line_starts: Vec<usize>,
+}
+
+impl<'src> LineIndex<'src> {
+    /// Create a new line index for the given source.
+    pub fn new(src: &'src str) -> Self {
+        let bytes = src.as_bytes();
+        let mut line_starts = vec![0];
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\r' if bytes.get(i + 1) == Some(&b'\n') => {
+                    line_starts.push(i + 2);
+                    i += 2;
+                }
+                b'\r' | b'\n' => {
+                    line_starts.push(i + 1);
+                    i += 1;
+                }
+                _ => i += 1,
+            }
+        }
+        Self { src, line_starts }
+    }
+
+    /// Get the source that this index was created from.
+    pub fn source(&self) -> &'src str {
+        self.src
+    }
+
+    /// Convert a byte offset into the source into a [`Position`].
+    pub fn position(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.src.len());
+        while !self.src.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let line_start = self.line_starts[line];
+        let character = self.src[line_start..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum::<usize>();
+        Position {
+            line: line as u32,
+            character: character as u32,
+        }
+    }
+
+    /// Convert a span over the source into a [`Range`].
+    pub fn range<S: Span<Offset = usize>>(&self, span: &S) -> Range {
+        Range {
+            start: self.position(span.start()),
+            end: self.position(span.end()),
+        }
+    }
+
+    /// Convert a [`Rich`] error produced by parsing the source into a [`Diagnostic`] with an error severity.
+    ///
+    /// If the `label` feature is enabled, each of the error's [contexts](Rich::contexts) becomes a related piece of
+    /// information reading "while parsing {label}".
+    pub fn diagnostic<T, S, L>(&self, err: &Rich<'_, T, S, L>) -> Diagnostic
+    where
+        T: fmt::Display,
+        S: Span<Offset = usize>,
+        L: fmt::Display,
+    {
+        self.diagnostic_with_severity(err, Severity::Error)
+    }
+
+    /// Like [`LineIndex::diagnostic`], but with the given severity. Useful in combination with
+    /// [`ParseResult::diagnostics`].
+    pub fn diagnostic_with_severity<T, S, L>(
+        &self,
+        err: &Rich<'_, T, S, L>,
+        severity: Severity,
+    ) -> Diagnostic
+    where
+        T: fmt::Display,
+        S: Span<Offset = usize>,
+        L: fmt::Display,
+    {
+        Diagnostic {
+            range: self.range(err.span()),
+            severity: severity.into(),
+            message: err.reason().to_string(),
+            #[cfg(feature = "label")]
+            related_information: err
+                .contexts()
+                .map(|(label, span)| DiagnosticRelatedInformation {
+                    range: self.range(span),
+                    message: alloc::format!("while parsing {}", label),
+                })
+                .collect(),
+            #[cfg(not(feature = "label"))]
+            related_information: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(line: u32, character: u32) -> Position {
+        Position { line, character }
+    }
+
+    #[test]
+    fn utf16_positions() {
+        let index = LineIndex::new("a🄯b\r\nc\rd\ne");
+
+        assert_eq!(index.position(0), pos(0, 0));
+        assert_eq!(index.position(1), pos(0, 1));
+        // Within the 4-byte, 2-code-unit emoji
+        assert_eq!(index.position(3), pos(0, 1));
+        assert_eq!(index.position(5), pos(0, 3));
+        // The `\r\n` terminator is a single line break
+        assert_eq!(index.position(6), pos(0, 4));
+        assert_eq!(index.position(8), pos(1, 0));
+        assert_eq!(index.position(10), pos(2, 0));
+        assert_eq!(index.position(12), pos(3, 0));
+        // Past the end of input
+        assert_eq!(index.position(100), pos(3, 1));
+    }
+
+    #[test]
+    fn multi_line_diagnostics() {
+        let src = "(1,\n 2)";
+        let parser = any::<_, extra::Err<Rich<char>>>()
+            .repeated()
+            .validate(|(), span, emitter| emitter.emit(Rich::custom(span, "bad list")));
+        let index = LineIndex::new(src);
+
+        let errs = parser.parse(src).into_errors();
+        let diag = index.diagnostic(&errs[0]);
+        assert_eq!(diag.message, "bad list");
+        assert_eq!(diag.severity, DiagnosticSeverity::Error);
+        assert_eq!(
+            diag.range,
+            Range {
+                start: pos(0, 0),
+                end: pos(1, 3),
+            }
+        );
+    }
+
+    #[test]
+    fn end_of_input_diagnostics() {
+        let src = "(1,\n 2";
+        let parser = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .padded()
+            .separated_by(just(','))
+            .collect::<Vec<_>>()
+            .delimited_by(just('('), just(')'));
+        let index = LineIndex::new(src);
+
+        let errs = parser.parse(src).into_errors();
+        let diag = index.diagnostic(&errs[0]);
+        assert_eq!(diag.message, "found end of input expected ',', or ')'");
+        assert_eq!(diag.range.start, pos(1, 2));
+        assert_eq!(diag.range.end, pos(1, 2));
+    }
+
+    #[test]
+    #[cfg(feature = "label")]
+    fn contexts_become_related_information() {
+        let src = "fn foo() {\n  1 +\n}";
+        let body = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .padded()
+            .then(just('+').padded().then(text::int(10).padded()).or_not())
+            .delimited_by(just('{'), just('}'))
+            .labelled("function body")
+            .as_context();
+        let parser = text::keyword("fn")
+            .padded()
+            .then(text::ident())
+            .then(just("()").padded())
+            .then(body);
+        let index = LineIndex::new(src);
+
+        let errs = parser.parse(src).into_errors();
+        let diag = index.diagnostic(&errs[0]);
+        assert_eq!(diag.range.start, pos(2, 0));
+        assert_eq!(
+            diag.related_information,
+            vec![DiagnosticRelatedInformation {
+                range: Range {
+                    start: pos(0, 9),
+                    end: pos(2, 1),
+                },
+                message: "while parsing function body".to_string(),
+            }],
+        );
+    }
+}
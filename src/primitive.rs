@@ -17,9 +17,17 @@
 
 use super::*;
 
+use super::recursive::OnceCell;
+
 /// See [`end`].
 pub struct End<I, E>(EmptyPhantom<(E, I)>);
 
+impl<I, E> fmt::Debug for End<I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("End").finish()
+    }
+}
+
 /// A parser that accepts only the end of input.
 ///
 /// The output type of this parser is `()`.
@@ -51,12 +59,30 @@ where
         }
     }
 
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::lookahead(Some(Vec::new()))
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, _gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError> {
+        Ok(())
+    }
+
     go_extra!(());
 }
 
 /// See [`empty`].
 pub struct Empty<I, E>(EmptyPhantom<(E, I)>);
 
+impl<I, E> fmt::Debug for Empty<I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Empty").finish()
+    }
+}
+
 /// A parser that parses no inputs.
 ///
 /// The output type of this parser is `()`.
@@ -81,6 +107,18 @@ where
         Ok(M::bind(|| ()))
     }
 
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::empty()
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, _gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError> {
+        Ok(())
+    }
+
     go_extra!(());
 }
 
@@ -127,6 +165,12 @@ impl<T: Clone, I, E> Clone for Just<T, I, E> {
     }
 }
 
+impl<T: fmt::Debug, I, E> fmt::Debug for Just<T, I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Just").field(&self.seq).finish()
+    }
+}
+
 /// A parser that accepts only the given input.
 ///
 /// The output type of this parser is `C`, the input or sequence that was provided.
@@ -168,6 +212,45 @@ where
         Self::go_cfg::<M>(self, inp, JustCfg::default())
     }
 
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::literal(
+            self.seq
+                .seq_iter()
+                .map(|tok| tok.borrow().clone())
+                .collect(),
+        )
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        for tok in self.seq.seq_iter() {
+            gen.push(tok.borrow().clone());
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate_where(
+        &self,
+        gen: &mut Generator<'_, I::Token>,
+        f: &dyn Fn(&T) -> bool,
+    ) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        if f(&self.seq) {
+            self.generate(gen)
+        } else {
+            Err(GenerateError::Exhausted)
+        }
+    }
+
     go_extra!(T);
 }
 
@@ -229,6 +312,12 @@ impl<T: Clone, I, E> Clone for OneOf<T, I, E> {
     }
 }
 
+impl<T: fmt::Debug, I, E> fmt::Debug for OneOf<T, I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("OneOf").field(&self.seq).finish()
+    }
+}
+
 /// A parser that accepts one of a sequence of specific inputs.
 ///
 /// The output type of this parser is `I`, the input that was found.
@@ -283,6 +372,49 @@ where
         }
     }
 
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::token(Some(
+            self.seq
+                .seq_iter()
+                .map(|tok| tok.borrow().clone())
+                .collect(),
+        ))
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.generate_where(gen, &|_| true)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate_where(
+        &self,
+        gen: &mut Generator<'_, I::Token>,
+        f: &dyn Fn(&I::Token) -> bool,
+    ) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        let toks = self
+            .seq
+            .seq_iter()
+            .map(|tok| tok.borrow().clone())
+            .filter(f)
+            .collect::<Vec<_>>();
+        if toks.is_empty() {
+            return Err(GenerateError::Exhausted);
+        }
+        let idx = gen.choose(toks.len())?;
+        gen.push(toks[idx].clone());
+        Ok(())
+    }
+
     go_extra!(I::Token);
 }
 
@@ -303,6 +435,12 @@ impl<T: Clone, I, E> Clone for NoneOf<T, I, E> {
     }
 }
 
+impl<T: fmt::Debug, I, E> fmt::Debug for NoneOf<T, I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("NoneOf").field(&self.seq).finish()
+    }
+}
+
 /// A parser that accepts any input that is *not* in a sequence of specific inputs.
 ///
 /// The output type of this parser is `I`, the input that was found.
@@ -352,6 +490,35 @@ where
         }
     }
 
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::token(None)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.generate_where(gen, &|_| true)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate_where(
+        &self,
+        gen: &mut Generator<'_, I::Token>,
+        f: &dyn Fn(&I::Token) -> bool,
+    ) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        let tok = gen.token_where(|tok| !self.seq.contains(tok) && f(tok))?;
+        gen.push(tok);
+        Ok(())
+    }
+
     go_extra!(I::Token);
 }
 
@@ -372,6 +539,12 @@ impl<F: Clone, I, O, E> Clone for Custom<F, I, O, E> {
     }
 }
 
+impl<F, I, O, E> fmt::Debug for Custom<F, I, O, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Custom").finish()
+    }
+}
+
 /// TODO
 ///
 /// # Example
@@ -420,22 +593,31 @@ where
 }
 
 /// See [`select!`].
-pub struct Select<F, I, O, E> {
+pub struct Select<F, I, O, E, L = ()> {
     filter: F,
+    // `()` when unlabelled, see [`Select::with_labels`]
+    labels: L,
     #[allow(dead_code)]
     phantom: EmptyPhantom<(E, O, I)>,
 }
 
-impl<F: Copy, I, O, E> Copy for Select<F, I, O, E> {}
-impl<F: Clone, I, O, E> Clone for Select<F, I, O, E> {
+impl<F: Copy, I, O, E, L: Copy> Copy for Select<F, I, O, E, L> {}
+impl<F: Clone, I, O, E, L: Clone> Clone for Select<F, I, O, E, L> {
     fn clone(&self) -> Self {
         Self {
             filter: self.filter.clone(),
+            labels: self.labels.clone(),
             phantom: EmptyPhantom::new(),
         }
     }
 }
 
+impl<F, I, O, E, L> fmt::Debug for Select<F, I, O, E, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Select").finish()
+    }
+}
+
 /// See [`select!`].
 pub const fn select<'a, F, I, O, E>(filter: F) -> Select<F, I, O, E>
 where
@@ -446,10 +628,24 @@ where
 {
     Select {
         filter,
+        labels: (),
         phantom: EmptyPhantom::new(),
     }
 }
 
+#[cfg(feature = "label")]
+impl<F, I, O, E> Select<F, I, O, E> {
+    /// Make errors produced by this parser expect the given labels (see [`LabelError`]), one for each of the patterns
+    /// that it accepts. This is usually done through the `#[label]` syntax of [`select!`].
+    pub fn with_labels<L>(self, labels: impl IntoIterator<Item = L>) -> Select<F, I, O, E, Vec<L>> {
+        Select {
+            filter: self.filter,
+            labels: labels.into_iter().collect(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
 impl<'a, I, O, E, F> ParserSealed<'a, I, O, E> for Select<F, I, O, E>
 where
     I: ValueInput<'a>,
@@ -473,26 +669,42 @@ where
         Err(())
     }
 
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::token(None)
+    }
+
     go_extra!(O);
 }
 
 /// See [`select_ref!`].
-pub struct SelectRef<F, I, O, E> {
+pub struct SelectRef<F, I, O, E, L = ()> {
     filter: F,
+    // `()` when unlabelled, see [`SelectRef::with_labels`]
+    labels: L,
     #[allow(dead_code)]
     phantom: EmptyPhantom<(E, O, I)>,
 }
 
-impl<F: Copy, I, O, E> Copy for SelectRef<F, I, O, E> {}
-impl<F: Clone, I, O, E> Clone for SelectRef<F, I, O, E> {
+impl<F: Copy, I, O, E, L: Copy> Copy for SelectRef<F, I, O, E, L> {}
+impl<F: Clone, I, O, E, L: Clone> Clone for SelectRef<F, I, O, E, L> {
     fn clone(&self) -> Self {
         Self {
             filter: self.filter.clone(),
+            labels: self.labels.clone(),
             phantom: EmptyPhantom::new(),
         }
     }
 }
 
+impl<F, I, O, E, L> fmt::Debug for SelectRef<F, I, O, E, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SelectRef").finish()
+    }
+}
+
 /// See [`select_ref!`].
 pub const fn select_ref<'a, F, I, O, E>(filter: F) -> SelectRef<F, I, O, E>
 where
@@ -503,10 +715,27 @@ where
 {
     SelectRef {
         filter,
+        labels: (),
         phantom: EmptyPhantom::new(),
     }
 }
 
+#[cfg(feature = "label")]
+impl<F, I, O, E> SelectRef<F, I, O, E> {
+    /// Make errors produced by this parser expect the given labels (see [`LabelError`]), one for each of the patterns
+    /// that it accepts. This is usually done through the `#[label]` syntax of [`select_ref!`].
+    pub fn with_labels<L>(
+        self,
+        labels: impl IntoIterator<Item = L>,
+    ) -> SelectRef<F, I, O, E, Vec<L>> {
+        SelectRef {
+            filter: self.filter,
+            labels: labels.into_iter().collect(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
 impl<'a, I, O, E, F> ParserSealed<'a, I, O, E> for SelectRef<F, I, O, E>
 where
     I: BorrowInput<'a>,
@@ -531,9 +760,122 @@ where
         Err(())
     }
 
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::token(None)
+    }
+
+    go_extra!(O);
+}
+
+#[cfg(feature = "label")]
+impl<'a, I, O, E, F, L> ParserSealed<'a, I, O, E> for Select<F, I, O, E, Vec<L>>
+where
+    I: ValueInput<'a>,
+    I::Token: Clone + 'a,
+    E: ParserExtra<'a, I>,
+    F: Fn(I::Token, I::Span) -> Option<O>,
+    L: Clone,
+    E::Error: LabelError<'a, I, L>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.offset();
+        let (at, found) = match inp.next_inner() {
+            (at, Some(tok)) => match (self.filter)(tok.clone(), inp.span_since(before)) {
+                Some(out) => return Ok(M::bind(|| out)),
+                None => (at, Some(tok)),
+            },
+            (at, None) => (at, None),
+        };
+        add_labelled_alts(inp, at, before, &self.labels, || {
+            found.clone().map(MaybeRef::Val)
+        });
+        Err(())
+    }
+
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::token(None)
+    }
+
     go_extra!(O);
 }
 
+#[cfg(feature = "label")]
+impl<'a, I, O, E, F, L> ParserSealed<'a, I, O, E> for SelectRef<F, I, O, E, Vec<L>>
+where
+    I: BorrowInput<'a>,
+    I::Token: 'a,
+    E: ParserExtra<'a, I>,
+    F: Fn(&'a I::Token, I::Span) -> Option<O>,
+    L: Clone,
+    E::Error: LabelError<'a, I, L>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let before = inp.offset();
+        let (at, found) = match inp.next_ref_inner() {
+            (at, Some(tok)) => match (self.filter)(tok, inp.span_since(before)) {
+                Some(out) => return Ok(M::bind(|| out)),
+                None => (at, Some(tok)),
+            },
+            (at, None) => (at, None),
+        };
+        add_labelled_alts(inp, at, before, &self.labels, || found.map(MaybeRef::Ref));
+        Err(())
+    }
+
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::token(None)
+    }
+
+    go_extra!(O);
+}
+
+// Report that the token at the current position matched none of the patterns of a labelled select, each of which
+// is described by a label
+#[cfg(feature = "label")]
+fn add_labelled_alts<'a, 'parse, I, E, L>(
+    inp: &mut InputRef<'a, 'parse, I, E>,
+    at: I::Offset,
+    before: input::Offset<'a, 'parse, I>,
+    labels: &[L],
+    found: impl Fn() -> Option<MaybeRef<'a, I::Token>>,
+) where
+    I: Input<'a>,
+    E: ParserExtra<'a, I>,
+    L: Clone,
+    E::Error: LabelError<'a, I, L>,
+{
+    if labels.is_empty() {
+        inp.add_alt(at, None, found(), inp.span_since(before));
+    }
+    for label in labels {
+        let mut err = E::Error::expected_found(None, found(), inp.span_since(before));
+        err.label_with(label.clone());
+        inp.add_alt_err(at, err);
+    }
+}
+
+/// Derive a label from the stringified pattern of a `select!` arm: the last segment of its path.
+#[cfg(feature = "label")]
+#[doc(hidden)]
+pub fn pattern_label(pattern: &'static str) -> &'static str {
+    let path = pattern
+        .split(|c: char| c == '(' || c == '{' || c == '|' || c.is_whitespace())
+        .next()
+        .unwrap_or(pattern);
+    path.rsplit("::").next().unwrap_or(path)
+}
+
 /// See [`any`].
 pub struct Any<I, E> {
     #[allow(dead_code)]
@@ -549,6 +891,12 @@ impl<I, E> Clone for Any<I, E> {
     }
 }
 
+impl<I, E> fmt::Debug for Any<I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Any").finish()
+    }
+}
+
 impl<'a, I, E> ParserSealed<'a, I, I::Token, E> for Any<I, E>
 where
     I: ValueInput<'a>,
@@ -567,6 +915,37 @@ where
         }
     }
 
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::token(None)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        let tok = gen.token()?;
+        gen.push(tok);
+        Ok(())
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate_where(
+        &self,
+        gen: &mut Generator<'_, I::Token>,
+        f: &dyn Fn(&I::Token) -> bool,
+    ) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        let tok = gen.token_where(f)?;
+        gen.push(tok);
+        Ok(())
+    }
+
     go_extra!(I::Token);
 }
 
@@ -607,6 +986,12 @@ impl<A: Clone, F: Clone> Clone for MapCtx<A, F> {
     }
 }
 
+impl<A: fmt::Debug, F> fmt::Debug for MapCtx<A, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("MapCtx").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A, F, Ctx> ParserSealed<'a, I, O, E> for MapCtx<A, F>
 where
     I: Input<'a>,
@@ -620,6 +1005,13 @@ where
         inp.with_ctx(&(self.mapper)(inp.ctx()), |inp| self.parser.go::<M>(inp))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
     go_extra!(O);
 }
 
@@ -674,6 +1066,12 @@ impl<I, O, E> Clone for Todo<I, O, E> {
     }
 }
 
+impl<I, O, E> fmt::Debug for Todo<I, O, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Todo").finish()
+    }
+}
+
 /// A parser that can be used wherever you need to implement a parser later.
 ///
 /// This parser is analogous to the [`todo!`] and [`unimplemented!`] macros, but will produce a panic when used to
@@ -726,7 +1124,7 @@ where
 /// See [`choice`].
 #[derive(Copy, Clone)]
 pub struct Choice<T> {
-    parsers: T,
+    pub(crate) parsers: T,
 }
 
 /// Parse using a tuple of many parsers, producing the output of the first to successfully parse.
@@ -815,6 +1213,31 @@ macro_rules! impl_choice_for_tuple {
                 Err(())
             }
 
+            fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+            where
+                I::Token: Clone + PartialEq,
+            {
+                let Choice { parsers: ($Head, $($X,)*), .. } = self;
+
+                let alts = vec![
+                    ($Head.summarize(cx), core::any::type_name::<$Head>()),
+                    $(($X.summarize(cx), core::any::type_name::<$X>())),*
+                ];
+                cx.alternatives(alts)
+            }
+
+            #[cfg(feature = "arbitrary")]
+            fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+            where
+                I::Token: Clone,
+            {
+                let Choice { parsers: ($Head, $($X,)*), .. } = self;
+
+                let alts: &[&dyn Fn(&mut Generator<'_, I::Token>) -> Result<(), GenerateError>] =
+                    &[&|gen| $Head.generate(gen), $(&|gen| $X.generate(gen)),*];
+                gen.alternatives(alts.len(), |gen, i| alts[i](gen))
+            }
+
             go_extra!(O);
         }
     };
@@ -830,6 +1253,21 @@ macro_rules! impl_choice_for_tuple {
                 self.parsers.0.go::<M>(inp)
             }
 
+            fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+            where
+                I::Token: Clone + PartialEq,
+            {
+                self.parsers.0.summarize(cx)
+            }
+
+            #[cfg(feature = "arbitrary")]
+            fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+            where
+                I::Token: Clone,
+            {
+                self.parsers.0.generate(gen)
+            }
+
             go_extra!(O);
         }
     };
@@ -865,6 +1303,388 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let alts = self
+            .parsers
+            .iter()
+            .map(|parser| (parser.summarize(cx), core::any::type_name::<A>()))
+            .collect::<Vec<_>>();
+        cx.alternatives(alts)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        gen.alternatives(N, |gen, i| self.parsers[i].generate(gen))
+    }
+
+    go_extra!(O);
+}
+
+// The alternatives of a choice to try for a particular next token of the input
+struct DispatchPlan<T> {
+    // The indices of the alternatives to try, in order
+    tried: Vec<usize>,
+    // The tokens expected by the alternatives skipped before each tried alternative, with one extra entry for those
+    // skipped after the last
+    skipped: Vec<Vec<T>>,
+}
+
+// A table mapping the next token of the input to the alternatives of a choice that might be able to match it
+struct DispatchTable<T> {
+    plans: Vec<DispatchPlan<T>>,
+    // The plan to use when the next token is one that some alternatives are known to begin with
+    by_token: HashMap<T, usize>,
+    // The plan to use for any other token, or the end of input
+    fallback: usize,
+    len: usize,
+}
+
+impl<T: Clone + Hash + Eq> DispatchTable<T> {
+    fn new(alts: impl IntoIterator<Item = Summary<T>>) -> Self {
+        // Alternatives may only be skipped if they must reject a token that they can't begin with without consuming
+        // input, and the error that they produce when they do is known
+        let firsts = alts
+            .into_iter()
+            .map(|alt| match alt.first {
+                Some(first) if alt.nullable == Some(false) && alt.predictable => Some(first),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut table = Self {
+            plans: Vec::new(),
+            by_token: HashMap::default(),
+            fallback: 0,
+            len: firsts.len(),
+        };
+        table.fallback = table.plan_for(&firsts, None);
+        for tok in firsts.iter().flatten().flatten() {
+            if !table.by_token.contains_key(tok) {
+                let plan = table.plan_for(&firsts, Some(tok));
+                table.by_token.insert(tok.clone(), plan);
+            }
+        }
+        table
+    }
+
+    fn plan_for(&mut self, firsts: &[Option<Vec<T>>], tok: Option<&T>) -> usize {
+        let mut plan = DispatchPlan {
+            tried: Vec::new(),
+            skipped: vec![Vec::new()],
+        };
+        for (idx, first) in firsts.iter().enumerate() {
+            match first {
+                Some(first) if tok.filter(|&tok| first.contains(tok)).is_none() => {
+                    let skipped = plan.skipped.last_mut().unwrap();
+                    for tok in first {
+                        if !skipped.contains(tok) {
+                            skipped.push(tok.clone());
+                        }
+                    }
+                }
+                _ => {
+                    plan.tried.push(idx);
+                    plan.skipped.push(Vec::new());
+                }
+            }
+        }
+        // Many tokens share the same alternatives
+        match self
+            .plans
+            .iter()
+            .position(|other| other.tried == plan.tried)
+        {
+            Some(idx) => idx,
+            None => {
+                self.plans.push(plan);
+                self.plans.len() - 1
+            }
+        }
+    }
+
+    fn plan(&self, tok: Option<&T>) -> &DispatchPlan<T> {
+        let idx = tok
+            .and_then(|tok| self.by_token.get(tok))
+            .copied()
+            .unwrap_or(self.fallback);
+        &self.plans[idx]
+    }
+}
+
+impl<T: Clone> DispatchPlan<T> {
+    // Report the error that a run of skipped alternatives would have produced had they been tried: that they expected
+    // one of the tokens they can begin with
+    fn add_skipped_alt<'a, I, E>(inp: &mut InputRef<'a, '_, I, E>, expected: &[T])
+    where
+        I: Input<'a, Token = T>,
+        T: 'a,
+        E: ParserExtra<'a, I>,
+    {
+        if expected.is_empty() {
+            return;
+        }
+        let before = inp.save();
+        let (at, found) = inp.next_maybe_inner();
+        let span = inp.span_since(before.offset());
+        inp.rewind(before);
+        inp.add_alt(
+            at,
+            expected.iter().map(|tok| Some(MaybeRef::Val(tok.clone()))),
+            found.map(|f| f.into()),
+            span,
+        );
+    }
+}
+
+/// See [`Choice::dispatched`].
+pub struct Dispatch<T, Tok> {
+    choice: Choice<T>,
+    table: OnceCell<DispatchTable<Tok>>,
+}
+
+impl<T: Clone, Tok> Clone for Dispatch<T, Tok> {
+    fn clone(&self) -> Self {
+        Self {
+            choice: self.choice.clone(),
+            // The table is cheap to rebuild, and only built on first use
+            table: OnceCell::new(),
+        }
+    }
+}
+
+impl<T, Tok> fmt::Debug for Dispatch<T, Tok>
+where
+    Choice<T>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Dispatch").field(&self.choice).finish()
+    }
+}
+
+impl<T> Choice<T> {
+    /// Skip alternatives that cannot match the next token of the input.
+    ///
+    /// The tokens that each alternative can begin with are determined from its structure (for example, from
+    /// [`just`] or [`one_of`]) the first time the parser is used. After that, the next token is used to look up the
+    /// alternatives that might match it, and only those are tried. Alternatives are always tried if their first token
+    /// can't be determined ahead of time (such as those created with [`any`], [`custom`], [`select!`] or
+    /// [`text::keyword`]), if they can match without consuming any input, or if their errors are changed (such as with
+    /// a label or [`Parser::map_err`]).
+    ///
+    /// An alternative is only skipped if it would have failed at the next token, and the error it would have produced
+    /// (that it expected one of the tokens it can begin with) is reported in its place, so the output and errors are
+    /// identical to those of the choice without dispatching. Skipped alternatives are not run at all, so any side
+    /// effects that they would have had while failing (such as changes made to the parser state) don't happen.
+    ///
+    /// This requires that the tokens of the input can be hashed. It is most useful for choices with many alternatives
+    /// that each begin with a distinct token, such as the alternatives of a lexer or the statements of a language.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::prelude::*;
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Token {
+    ///     Eq,
+    ///     Assign,
+    ///     Arrow,
+    ///     Minus,
+    ///     Digit,
+    /// }
+    ///
+    /// let token = choice((
+    ///     just::<_, _, extra::Err<Simple<char>>>("==").to(Token::Eq),
+    ///     just('=').to(Token::Assign),
+    ///     just("->").to(Token::Arrow),
+    ///     just('-').to(Token::Minus),
+    ///     one_of('0'..='9').to(Token::Digit),
+    /// ))
+    ///     .dispatched()
+    ///     .padded()
+    ///     .repeated()
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     token.parse("= -> 4 == -").into_result(),
+    ///     Ok(vec![Token::Assign, Token::Arrow, Token::Digit, Token::Eq, Token::Minus]),
+    /// );
+    /// ```
+    pub fn dispatched<Tok>(self) -> Dispatch<T, Tok> {
+        Dispatch {
+            choice: self,
+            table: OnceCell::new(),
+        }
+    }
+}
+
+macro_rules! impl_dispatch_for_tuple {
+    () => {};
+    ($head:ident $($X:ident)*) => {
+        impl_dispatch_for_tuple!($($X)*);
+        impl_dispatch_for_tuple!(~ $head $($X)*);
+    };
+    (~ $Head:ident $($X:ident)+) => {
+        #[allow(unused_variables, non_snake_case)]
+        impl<'a, I, E, $Head, $($X),*, O> ParserSealed<'a, I, O, E> for Dispatch<($Head, $($X,)*), I::Token>
+        where
+            I: Input<'a>,
+            I::Token: Clone + Hash + Eq,
+            E: ParserExtra<'a, I>,
+            $Head: Parser<'a, I, O, E>,
+            $($X: Parser<'a, I, O, E>),*
+        {
+            #[inline]
+            // The index of the alternative is incremented after the last one too
+            #[allow(unused_assignments)]
+            fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+                let Choice { parsers: ($Head, $($X,)*), .. } = &self.choice;
+
+                let table = self.table.get_or_init(|| DispatchTable::new([
+                    $Head.summarize(&mut Analyzer::new()),
+                    $($X.summarize(&mut Analyzer::new())),*
+                ]));
+                let plan = table.plan(inp.peek_maybe().as_deref());
+                if plan.tried.len() == table.len {
+                    return self.choice.go::<M>(inp);
+                }
+
+                let before = inp.save();
+                let mut tried = plan.tried.iter().zip(&plan.skipped).peekable();
+                let mut idx = 0;
+
+                if let Some((_, skipped)) = tried.next_if(|(i, _)| **i == idx) {
+                    DispatchPlan::add_skipped_alt(inp, skipped);
+                    match $Head.go::<M>(inp) {
+                        Ok(out) => return Ok(out),
+                        Err(()) => inp.rewind(before),
+                    }
+                }
+                idx += 1;
+
+                $(
+                    if let Some((_, skipped)) = tried.next_if(|(i, _)| **i == idx) {
+                        DispatchPlan::add_skipped_alt(inp, skipped);
+                        match $X.go::<M>(inp) {
+                            Ok(out) => return Ok(out),
+                            Err(()) => inp.rewind(before),
+                        }
+                    }
+                    idx += 1;
+                )*
+
+                DispatchPlan::add_skipped_alt(inp, plan.skipped.last().unwrap());
+                Err(())
+            }
+
+            fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+            where
+                I::Token: Clone + PartialEq,
+            {
+                self.choice.summarize(cx)
+            }
+
+            #[cfg(feature = "arbitrary")]
+            fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+            where
+                I::Token: Clone,
+            {
+                self.choice.generate(gen)
+            }
+
+            go_extra!(O);
+        }
+    };
+    (~ $Head:ident) => {
+        impl<'a, I, E, $Head, O> ParserSealed<'a, I, O, E> for Dispatch<($Head,), I::Token>
+        where
+            I: Input<'a>,
+            I::Token: Clone + Hash + Eq,
+            E: ParserExtra<'a, I>,
+            $Head:  Parser<'a, I, O, E>,
+        {
+            #[inline]
+            fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+                self.choice.go::<M>(inp)
+            }
+
+            fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+            where
+                I::Token: Clone + PartialEq,
+            {
+                self.choice.summarize(cx)
+            }
+
+            #[cfg(feature = "arbitrary")]
+            fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+            where
+                I::Token: Clone,
+            {
+                self.choice.generate(gen)
+            }
+
+            go_extra!(O);
+        }
+    };
+}
+
+impl_dispatch_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ R_ S_ T_ U_ V_ W_ X_ Y_ Z_);
+
+impl<'a, A, I, O, E, const N: usize> ParserSealed<'a, I, O, E> for Dispatch<[A; N], I::Token>
+where
+    A: Parser<'a, I, O, E>,
+    I: Input<'a>,
+    I::Token: Clone + Hash + Eq,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, O> {
+        let parsers = &self.choice.parsers;
+        let table = self.table.get_or_init(|| {
+            DispatchTable::new(
+                parsers
+                    .iter()
+                    .map(|parser| parser.summarize(&mut Analyzer::new())),
+            )
+        });
+        let plan = table.plan(inp.peek_maybe().as_deref());
+        if plan.tried.len() == table.len {
+            return self.choice.go::<M>(inp);
+        }
+
+        let before = inp.save();
+        for (&idx, skipped) in plan.tried.iter().zip(&plan.skipped) {
+            DispatchPlan::add_skipped_alt(inp, skipped);
+            match parsers[idx].go::<M>(inp) {
+                Ok(out) => return Ok(out),
+                Err(()) => inp.rewind(before),
+            }
+        }
+
+        DispatchPlan::add_skipped_alt(inp, plan.skipped.last().unwrap());
+        Err(())
+    }
+
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.choice.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.choice.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -903,6 +1723,27 @@ where
         Ok(M::array(unsafe { MaybeUninitExt::array_assume_init(arr) }))
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parsers
+            .iter()
+            .fold(Summary::empty(), |summary, parser| {
+                cx.then(summary, |cx| parser.summarize(cx))
+            })
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parsers
+            .iter()
+            .try_for_each(|parser| parser.generate(gen))
+    }
+
     go_extra!([O; N]);
 }
 
@@ -960,6 +1801,33 @@ macro_rules! impl_group_for_tuple {
                 Ok(flatten_map!(<M> $($X)*))
             }
 
+            fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+            where
+                I::Token: Clone + PartialEq,
+            {
+                let Group { parsers: ($($X,)*) } = self;
+
+                let summary = Summary::empty();
+                $(
+                    let summary = cx.then(summary, |cx| $X.summarize(cx));
+                )*
+                summary
+            }
+
+            #[cfg(feature = "arbitrary")]
+            fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+            where
+                I::Token: Clone,
+            {
+                let Group { parsers: ($($X,)*) } = self;
+
+                $(
+                    $X.generate(gen)?;
+                )*
+
+                Ok(())
+            }
+
             go_extra!(($($O,)*));
         }
     };
@@ -993,3 +1861,50 @@ impl_group_for_tuple! {
     Y_ OY
     Z_ OZ
 }
+
+macro_rules! impl_debug_for_tuple {
+    () => {};
+    ($head:ident $($X:ident)*) => {
+        impl_debug_for_tuple!($($X)*);
+        impl_debug_for_tuple!(~ $head $($X)*);
+    };
+    (~ $($X:ident)+) => {
+        #[allow(non_snake_case)]
+        impl<$($X: fmt::Debug),+> fmt::Debug for Choice<($($X,)+)> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let Choice { parsers: ($($X,)+) } = self;
+                f.debug_tuple("Choice")$(.field($X))+.finish()
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<$($X: fmt::Debug),+> fmt::Debug for Group<($($X,)+)> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let Group { parsers: ($($X,)+) } = self;
+                f.debug_tuple("Group")$(.field($X))+.finish()
+            }
+        }
+    };
+}
+
+impl_debug_for_tuple!(A_ B_ C_ D_ E_ F_ G_ H_ I_ J_ K_ L_ M_ N_ O_ P_ Q_ R_ S_ T_ U_ V_ W_ X_ Y_ Z_);
+
+impl<A: fmt::Debug, const N: usize> fmt::Debug for Choice<[A; N]> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tuple = f.debug_tuple("Choice");
+        for parser in &self.parsers {
+            tuple.field(parser);
+        }
+        tuple.finish()
+    }
+}
+
+impl<A: fmt::Debug, const N: usize> fmt::Debug for Group<[A; N]> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tuple = f.debug_tuple("Group");
+        for parser in &self.parsers {
+            tuple.field(parser);
+        }
+        tuple.finish()
+    }
+}
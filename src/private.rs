@@ -201,6 +201,43 @@ pub trait ParserSealed<'a, I: Input<'a>, O, E: ParserExtra<'a, I>> {
     fn go_emit(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<Emit, O>;
     fn go_check(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<Check, O>;
 
+    // The name given to this parser with `Parser::named`, if any. Used to describe parsers that can't implement
+    // `Debug` structurally, such as `Boxed` and `Recursive`.
+    fn name(&self) -> Option<&'static str> {
+        None
+    }
+
+    // Summarise the inputs that this parser accepts, for static analysis. See `Parser::analyze`.
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::opaque()
+    }
+
+    // Generate an input that this parser should accept. See `generate::Generator`.
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, _gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        Err(GenerateError::Unsupported(core::any::type_name::<Self>()))
+    }
+
+    // Generate an input that this parser should accept and for which the output satisfies the given filter. Only
+    // implemented by parsers that output a single token, for which rejection sampling is viable.
+    #[cfg(feature = "arbitrary")]
+    fn generate_where(
+        &self,
+        _gen: &mut Generator<'_, I::Token>,
+        _f: &dyn Fn(&O) -> bool,
+    ) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        Err(GenerateError::Unsupported(core::any::type_name::<Self>()))
+    }
+
     fn boxed<'b>(self) -> Boxed<'a, 'b, I, O, E>
     where
         Self: MaybeSync + Sized + 'a + 'b,
@@ -250,6 +287,25 @@ where
         inp: &mut InputRef<'a, '_, I, E>,
         state: &mut Self::IterState<M>,
     ) -> IPResult<M, O>;
+
+    // Summarise the inputs that this parser accepts, for static analysis. See `Parser::analyze`.
+    #[doc(hidden)]
+    fn summarize_iter(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        Summary::opaque()
+    }
+
+    // Generate an input that this parser should accept. See `generate::Generator`.
+    #[doc(hidden)]
+    #[cfg(feature = "arbitrary")]
+    fn generate_iter(&self, _gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        Err(GenerateError::Unsupported(core::any::type_name::<Self>()))
+    }
 }
 
 pub trait ConfigIterParserSealed<'a, I, O, E>: IterParserSealed<'a, I, O, E>
@@ -0,0 +1,312 @@
+//! Grammar-level profiling, for finding out which parts of a grammar parsing spends its time in.
+//!
+//! Profiling is enabled for a single parse with [`ParseOptions::profile`]. While enabled, every parser given a name
+//! with [`Parser::named`] records how often it was invoked, how often it failed, how much input it consumed and how
+//! long it took. The resulting [`Profile`] is available through [`ParseResult::profile`], and can be displayed as a
+//! table or written out as 'folded stacks' for use with flamegraph tools such as
+//! [`inferno`](https://github.com/jonhoo/inferno).
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, ParseOptions};
+//! let digits = text::digits::<_, _, extra::Default>(10).slice().named("digits");
+//! let sum = digits
+//!     .separated_by(just('+'))
+//!     .collect::<Vec<_>>()
+//!     .named("sum");
+//!
+//! let result = sum.parse_with_options("1+23+456", ParseOptions::new().profile());
+//! let profile = result.profile().unwrap();
+//!
+//! let digits = profile.entry("digits").unwrap();
+//! assert_eq!(digits.calls(), 3);
+//! assert_eq!(digits.consumed(), 6);
+//!
+//! // Prints a table of statistics, one row per named parser
+//! println!("{}", profile);
+//! ```
+
+use super::*;
+
+use std::time::{Duration, Instant};
+
+/// Statistics recorded for a single named parser. See [`Profile`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Entry {
+    name: &'static str,
+    calls: u64,
+    backtracks: u64,
+    consumed: usize,
+    total: Duration,
+    own: Duration,
+}
+
+impl Entry {
+    /// The name of the parser (see [`Parser::named`]).
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The number of times that the parser was invoked.
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// The number of times that the parser failed, causing the parser that invoked it to backtrack.
+    pub fn backtracks(&self) -> u64 {
+        self.backtracks
+    }
+
+    /// The total amount of input consumed by successful invocations of the parser, measured in offsets of the input
+    /// (bytes, for `&str` inputs).
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// The total time spent in the parser, including time spent in other named parsers that it invoked.
+    ///
+    /// Time spent in recursive invocations of the parser is only counted once.
+    pub fn total_time(&self) -> Duration {
+        self.total
+    }
+
+    /// The time spent in the parser, excluding time spent in other named parsers that it invoked.
+    pub fn self_time(&self) -> Duration {
+        self.own
+    }
+}
+
+/// The statistics recorded while profiling a parse. See the [module-level documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Profile {
+    entries: Vec<Entry>,
+    // Self time spent in each distinct stack of named parsers, as indices into `entries`
+    stacks: Vec<(Vec<usize>, Duration)>,
+}
+
+impl Profile {
+    /// Get the statistics for every named parser that was invoked, in order of decreasing total time.
+    pub fn entries(&self) -> impl ExactSizeIterator<Item = &Entry> {
+        self.entries.iter()
+    }
+
+    /// Get the statistics for the named parser with the given name, if it was invoked.
+    pub fn entry(&self, name: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    /// Write the self time of every stack of named parsers in the 'folded stacks' format, with times given in
+    /// nanoseconds.
+    ///
+    /// Each line is made up of the names of the parsers in the stack, separated by `;`, followed by a space and the
+    /// time. This format is understood by most flamegraph tools.
+    pub fn write_folded<W: std::io::Write>(&self, mut w: W) -> std::io::Result<()> {
+        for (stack, time) in &self.stacks {
+            let names = stack
+                .iter()
+                .map(|idx| self.entries[*idx].name)
+                .collect::<Vec<_>>();
+            writeln!(w, "{} {}", names.join(";"), time.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .entries
+            .iter()
+            .map(|entry| entry.name.chars().count())
+            .chain(Some(4))
+            .max()
+            .unwrap_or(0);
+        writeln!(
+            f,
+            "{:<width$}  {:>10}  {:>10}  {:>10}  {:>12}  {:>12}",
+            "name",
+            "calls",
+            "backtracks",
+            "consumed",
+            "total",
+            "self",
+            width = width,
+        )?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{:<width$}  {:>10}  {:>10}  {:>10}  {:>12}  {:>12}",
+                entry.name,
+                entry.calls,
+                entry.backtracks,
+                entry.consumed,
+                alloc::format!("{:.3?}", entry.total),
+                alloc::format!("{:.3?}", entry.own),
+                width = width,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+struct Frame {
+    entry: usize,
+    start: Instant,
+    // Time spent in named parsers invoked by this one
+    children: Duration,
+}
+
+/// Records statistics for named parsers during a parse.
+#[derive(Default)]
+pub(crate) struct Profiler {
+    entries: Vec<Entry>,
+    indices: HashMap<&'static str, usize>,
+    stack: Vec<Frame>,
+    stacks: HashMap<Vec<usize>, Duration>,
+}
+
+impl Profiler {
+    pub(crate) fn enter(&mut self, name: &'static str) {
+        let entries = &mut self.entries;
+        let entry = *self.indices.entry(name).or_insert_with(|| {
+            entries.push(Entry {
+                name,
+                calls: 0,
+                backtracks: 0,
+                consumed: 0,
+                total: Duration::ZERO,
+                own: Duration::ZERO,
+            });
+            entries.len() - 1
+        });
+        self.entries[entry].calls += 1;
+        self.stack.push(Frame {
+            entry,
+            start: Instant::now(),
+            children: Duration::ZERO,
+        });
+    }
+
+    pub(crate) fn exit(&mut self, consumed: Option<usize>) {
+        let frame = self
+            .stack
+            .pop()
+            .expect("profiled parser exited without entering");
+        let elapsed = frame.start.elapsed();
+        let own = elapsed.saturating_sub(frame.children);
+
+        let key = self
+            .stack
+            .iter()
+            .map(|frame| frame.entry)
+            .chain(Some(frame.entry))
+            .collect();
+        *self.stacks.entry(key).or_insert(Duration::ZERO) += own;
+
+        let recursive = self.stack.iter().any(|outer| outer.entry == frame.entry);
+        let entry = &mut self.entries[frame.entry];
+        match consumed {
+            Some(consumed) => entry.consumed += consumed,
+            None => entry.backtracks += 1,
+        }
+        entry.own += own;
+        if !recursive {
+            entry.total += elapsed;
+        }
+        if let Some(parent) = self.stack.last_mut() {
+            parent.children += elapsed;
+        }
+    }
+
+    pub(crate) fn finish(self) -> Profile {
+        let mut order = (0..self.entries.len()).collect::<Vec<_>>();
+        order.sort_by(|a, b| self.entries[*b].total.cmp(&self.entries[*a].total));
+        // Entries are reordered, so stacks need to refer to their new positions
+        let mut positions = vec![0; order.len()];
+        for (pos, idx) in order.iter().enumerate() {
+            positions[*idx] = pos;
+        }
+
+        let mut stacks = self
+            .stacks
+            .into_iter()
+            .map(|(stack, time)| (stack.into_iter().map(|idx| positions[idx]).collect(), time))
+            .collect::<Vec<(Vec<usize>, Duration)>>();
+        stacks.sort();
+
+        let mut entries = self.entries.into_iter().map(Some).collect::<Vec<_>>();
+        Profile {
+            entries: order
+                .into_iter()
+                .map(|idx| entries[idx].take().unwrap())
+                .collect(),
+            stacks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::*, ParseOptions};
+
+    #[test]
+    fn recursive_profile() {
+        let expr = recursive::<_, _, extra::Default, _, _>(|expr| {
+            let atom = text::int(10)
+                .ignored()
+                .named("int")
+                .or(expr.delimited_by(just('('), just(')')));
+            atom.clone()
+                .then(just('+').ignore_then(atom).repeated())
+                .ignored()
+                .named("expr")
+        });
+
+        let result = expr.parse_with_options("1+(2+(3))", ParseOptions::new().profile());
+        assert!(result.has_output());
+        let profile = result.profile().unwrap();
+
+        let expr = profile.entry("expr").unwrap();
+        assert_eq!(expr.calls(), 3);
+        assert_eq!(expr.backtracks(), 0);
+        assert_eq!(expr.consumed(), 9 + 5 + 1);
+
+        let int = profile.entry("int").unwrap();
+        // `int` fails before each parenthesised expression
+        assert_eq!(int.calls(), 5);
+        assert_eq!(int.backtracks(), 2);
+        assert_eq!(int.consumed(), 3);
+
+        // The outermost expression is the slowest, since it includes everything else
+        assert_eq!(profile.entries().next().unwrap().name(), "expr");
+        assert!(expr.total_time() >= int.total_time());
+
+        let mut folded = Vec::new();
+        profile.write_folded(&mut folded).unwrap();
+        let folded = String::from_utf8(folded).unwrap();
+        let stacks = folded
+            .lines()
+            .map(|line| line.rsplit_once(' ').unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stacks,
+            [
+                "expr",
+                "expr;expr",
+                "expr;expr;expr",
+                "expr;expr;expr;int",
+                "expr;expr;int",
+                "expr;int"
+            ],
+        );
+
+        // Profiling is off by default
+        assert!(
+            recursive::<_, _, extra::Default, _, _>(|_| text::int(10).named("int"))
+                .parse("1")
+                .profile()
+                .is_none()
+        );
+    }
+}
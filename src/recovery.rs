@@ -4,9 +4,9 @@ use super::*;
 
 /// A trait implemented by error recovery strategies. See [`Parser::recover_with`].
 ///
-/// This trait is sealed and so cannot be implemented by other crates because it has an unstable API. This may
-/// eventually change. For now, if you wish to implement a new strategy, consider using [`via_parser`] or
-/// [opening an issue/PR](https://github.com/zesterer/chumsky/issues/new).
+/// This trait is sealed and so cannot be implemented by other crates because it has an unstable API. If you wish to
+/// implement a new strategy, consider using [`via_parser`] or implementing `ExtStrategy` from the `extension` API
+/// (requires the `extension` feature).
 pub trait Strategy<'a, I: Input<'a>, O, E: ParserExtra<'a, I> = extra::Default>: Sealed {
     // Attempt to recover from a parsing failure.
     // The strategy should properly handle the alt error but is not required to handle rewinding.
@@ -28,6 +28,12 @@ pub fn via_parser<A>(parser: A) -> ViaParser<A> {
 }
 
 impl<A> Sealed for ViaParser<A> {}
+
+impl<A: fmt::Debug> fmt::Debug for ViaParser<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ViaParser").field(&self.0).finish()
+    }
+}
 impl<'a, I, O, E, A> Strategy<'a, I, O, E> for ViaParser<A>
 where
     I: Input<'a>,
@@ -52,6 +58,36 @@ where
     }
 }
 
+/// Information about any error recovery that took place while parsing a pattern. See [`Parser::map_with_recovery`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecoveryInfo<S> {
+    pub(crate) span: S,
+    pub(crate) skipped: Option<S>,
+}
+
+impl<S> RecoveryInfo<S> {
+    /// The span of the pattern.
+    pub fn span(&self) -> &S {
+        &self.span
+    }
+
+    /// Whether any part of the pattern's output was produced by error recovery.
+    ///
+    /// This is useful for suppressing cascading errors in later passes (such as type checking) that would otherwise
+    /// be reported against placeholder values produced by a recovery strategy.
+    pub fn recovered(&self) -> bool {
+        self.skipped.is_some()
+    }
+
+    /// The span of input consumed while recovering from errors within the pattern, if any recovery took place.
+    ///
+    /// If recovery took place without consuming any input (for example, after inserting a missing delimiter), this is
+    /// an empty span at the point of recovery. If several recoveries took place, this covers all of them.
+    pub fn skipped(&self) -> Option<&S> {
+        self.skipped.as_ref()
+    }
+}
+
 /// See [`Parser::recover_with`].
 #[derive(Copy, Clone)]
 pub struct RecoverWith<A, S> {
@@ -59,6 +95,15 @@ pub struct RecoverWith<A, S> {
     pub(crate) strategy: S,
 }
 
+impl<A: fmt::Debug, S: fmt::Debug> fmt::Debug for RecoverWith<A, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("RecoverWith")
+            .field(&self.parser)
+            .field(&self.strategy)
+            .finish()
+    }
+}
+
 impl<'a, I, O, E, A, S> ParserSealed<'a, I, O, E> for RecoverWith<A, S>
 where
     I: Input<'a>,
@@ -72,8 +117,14 @@ where
             Ok(out) => Ok(out),
             Err(()) => {
                 inp.rewind(before);
+                if !inp.errors.begin_recovery(inp.offset) {
+                    return Err(());
+                }
                 match self.strategy.recover::<M, _>(inp, &self.parser) {
-                    Ok(out) => Ok(out),
+                    Ok(out) => {
+                        inp.mark_recovered(before.offset);
+                        Ok(out)
+                    }
                     Err(()) => {
                         // Reset to before fallback attempt
                         inp.rewind(before);
@@ -84,6 +135,21 @@ where
         }
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx).recovering()
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
     go_extra!(O);
 }
 
@@ -96,6 +162,15 @@ pub struct SkipThenRetryUntil<S, U> {
 }
 
 impl<S, U> Sealed for SkipThenRetryUntil<S, U> {}
+
+impl<S: fmt::Debug, U: fmt::Debug> fmt::Debug for SkipThenRetryUntil<S, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SkipThenRetryUntil")
+            .field(&self.skip)
+            .field(&self.until)
+            .finish()
+    }
+}
 impl<'a, I, O, E, S, U> Strategy<'a, I, O, E> for SkipThenRetryUntil<S, U>
 where
     I: ValueInput<'a>,
@@ -155,6 +230,15 @@ pub struct SkipUntil<S, U, F> {
 }
 
 impl<S, U, F> Sealed for SkipUntil<S, U, F> {}
+
+impl<S: fmt::Debug, U: fmt::Debug, F> fmt::Debug for SkipUntil<S, U, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SkipUntil")
+            .field(&self.skip)
+            .field(&self.until)
+            .finish()
+    }
+}
 impl<'a, I, O, E, S, U, F> Strategy<'a, I, O, E> for SkipUntil<S, U, F>
 where
     I: ValueInput<'a>,
@@ -197,6 +281,469 @@ pub fn skip_until<S, U, F>(skip: S, until: U, fallback: F) -> SkipUntil<S, U, F>
     }
 }
 
+/// A repair made to the input by the [`repair`] strategy.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Edit<'a, T> {
+    /// A missing token was inserted.
+    Insert(MaybeRef<'a, T>),
+    /// An unexpected token was deleted.
+    Delete(MaybeRef<'a, T>),
+}
+
+impl<'a, T> Edit<'a, T> {
+    /// Get the token that was inserted or deleted.
+    pub fn token(&self) -> &T {
+        match self {
+            Self::Insert(tok) | Self::Delete(tok) => tok,
+        }
+    }
+
+    /// Transform this edit's token using the given function.
+    pub fn map_token<U, F: FnMut(T) -> U>(self, mut f: F) -> Edit<'a, U>
+    where
+        T: Clone,
+    {
+        match self {
+            Self::Insert(tok) => Edit::Insert(f(tok.into_inner()).into()),
+            Self::Delete(tok) => Edit::Delete(f(tok.into_inner()).into()),
+        }
+    }
+
+    /// Convert this edit into an owned version of itself by cloning the borrowed token, if necessary.
+    pub fn into_owned<'b>(self) -> Edit<'b, T>
+    where
+        T: Clone,
+    {
+        match self {
+            Self::Insert(tok) => Edit::Insert(tok.into_owned()),
+            Self::Delete(tok) => Edit::Delete(tok.into_owned()),
+        }
+    }
+}
+
+/// See [`repair`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct Repair<F, C> {
+    insert: F,
+    cost: C,
+}
+
+impl<F, C> Sealed for Repair<F, C> {}
+
+impl<F, C> fmt::Debug for Repair<F, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Repair").finish()
+    }
+}
+impl<'a, I, O, E, F, C> Strategy<'a, I, O, E> for Repair<F, C>
+where
+    I: ValueInput<'a>,
+    I::Token: Clone,
+    F: Fn(I::Token) -> O,
+    C: Fn(&Edit<'a, I::Token>) -> Option<usize>,
+    E: ParserExtra<'a, I>,
+{
+    fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        parser: &P,
+    ) -> PResult<M, O> {
+        let alt = inp.errors.alt.take().expect("error but no alt?");
+
+        let before = inp.save();
+        let start = inp.offset();
+        let (after, found) = inp.next_maybe_inner();
+        let found = found.map(|found| -> MaybeRef<'a, I::Token> { found.into() });
+        let found_span = inp.span_since(start);
+        inp.rewind(before);
+
+        // Only errors that occur at the very first token can be repaired
+        if alt.pos.into() != after.into() {
+            inp.errors.alt = Some(alt);
+            return Err(());
+        }
+
+        let insertion = alt
+            .err
+            .expected_tokens()
+            .into_iter()
+            .filter_map(|tok| {
+                let edit = Edit::Insert(tok.clone());
+                (self.cost)(&edit).map(|cost| (cost, edit))
+            })
+            // `min_by_key` picks the last of several equal elements, so reverse to prefer earlier expected tokens
+            .rev()
+            .min_by_key(|(cost, _)| *cost);
+
+        if let Some(found) = found {
+            let edit = Edit::Delete(found);
+            let deletion_cost = (self.cost)(&edit);
+            // Deletion is checked by retrying the parser while insertion is not, so deletion wins ties
+            let try_deletion = match (deletion_cost, &insertion) {
+                (Some(del), Some((ins, _))) => del <= *ins,
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            if try_deletion {
+                inp.skip();
+                let before_retry = inp.save();
+                if let Some(out) = parser.go::<M>(inp).ok().filter(|_| {
+                    inp.errors
+                        .secondary_errors_since(before_retry.err_count)
+                        .is_empty()
+                }) {
+                    inp.emit(inp.offset, alt.err.repaired(edit, found_span));
+                    return Ok(out);
+                }
+                inp.errors.alt.take();
+                inp.rewind(before);
+            }
+        }
+
+        match insertion {
+            Some((_, edit)) => {
+                let tok = edit.token().clone();
+                let span = inp.span_since(start);
+                inp.emit(inp.offset, alt.err.repaired(edit, span));
+                Ok(M::bind(|| (self.insert)(tok)))
+            }
+            None => {
+                inp.errors.alt = Some(alt);
+                Err(())
+            }
+        }
+    }
+}
+
+/// A recovery strategy that repairs the input by inserting a single missing token or deleting a single unexpected
+/// token, whichever is cheapest.
+///
+/// This strategy only applies when the parser fails at the very first token it encounters. As such, it works best when
+/// applied to small parsers, such as the terminators and delimiters of a larger pattern: many syntax errors are simply
+/// a missing `;` or `)`.
+///
+/// Candidate tokens for insertion are taken from the tokens that the failing parser expected (see
+/// [`Error::expected_tokens`]). When a token is inserted, the output of the parser is generated by passing the token
+/// to `insert`. Deletion skips the unexpected token and tries the parser again, succeeding only if the parser then
+/// succeeds without producing any further errors.
+///
+/// `cost` gives the cost of each potential [`Edit`], or `None` if the edit should never be made. The cheapest edit
+/// wins. Deletion is preferred over insertion of equal cost because it has been checked against the input that
+/// follows, but if retrying the parser fails then the cheapest insertion is used instead. An error describing the edit
+/// is emitted (see [`Error::repaired`]).
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, recovery::Edit};
+/// let stmt = text::ident::<_, _, extra::Err<Rich<char>>>()
+///     .then_ignore(just(';').recover_with(repair(|tok| tok, |edit: &Edit<char>| match edit {
+///         // Never delete the start of another statement
+///         Edit::Delete(tok) if tok.is_alphabetic() => None,
+///         _ => Some(1),
+///     })))
+///     .padded();
+/// let stmts = stmt.repeated().collect::<Vec<_>>();
+///
+/// // Missing `;`
+/// let (out, errs) = stmts.parse("foo bar;").into_output_errors();
+/// assert_eq!(out, Some(vec!["foo", "bar"]));
+/// assert_eq!(errs[0].to_string(), "inserted missing ';'");
+///
+/// // Stray `)`
+/// let (out, errs) = stmts.parse("foo); bar;").into_output_errors();
+/// assert_eq!(out, Some(vec!["foo", "bar"]));
+/// assert_eq!(errs[0].to_string(), "deleted unexpected ')'");
+/// ```
+pub fn repair<F, C>(insert: F, cost: C) -> Repair<F, C> {
+    Repair { insert, cost }
+}
+
+/// A set of synchronising tokens for [`SyncTo`]. See [`sync_to`].
+#[derive(Copy, Clone)]
+pub struct SyncTokens<S>(S);
+
+impl<S: fmt::Debug> fmt::Debug for SyncTokens<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SyncTokens").field(&self.0).finish()
+    }
+}
+
+/// A parser that marks synchronisation points for [`SyncTo`]. See [`SyncTo::parser`].
+#[derive(Copy, Clone)]
+pub struct SyncParser<P>(P);
+
+impl<P: fmt::Debug> fmt::Debug for SyncParser<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SyncParser").field(&self.0).finish()
+    }
+}
+
+/// See [`sync_to`].
+#[must_use]
+#[derive(Copy, Clone)]
+pub struct SyncTo<S, F, D = ()> {
+    sync: S,
+    fallback: F,
+    nesting: D,
+}
+
+impl<S: fmt::Debug, F, D: fmt::Debug> fmt::Debug for SyncTo<S, F, D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SyncTo")
+            .field(&self.sync)
+            .field(&self.nesting)
+            .finish()
+    }
+}
+
+impl<S, F> SyncTo<S, F> {
+    /// Make this strategy aware of nesting: synchronising tokens that appear between the `open` and `close` delimiters
+    /// are skipped over, and an unmatched closing delimiter is treated as a synchronisation point.
+    ///
+    /// Delimiters are not required to be correctly paired: nesting depth is tracked by counting opening and closing
+    /// delimiters of any kind.
+    pub fn nested<O, C>(self, open: O, close: C) -> SyncTo<S, F, (O, C)> {
+        SyncTo {
+            sync: self.sync,
+            fallback: self.fallback,
+            nesting: (open, close),
+        }
+    }
+}
+
+impl<P, F> SyncTo<SyncParser<P>, F> {
+    /// Like [`sync_to`], but synchronise at any position where the given parser matches instead of at a set of
+    /// tokens. The input matched by the parser is not consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chumsky::{prelude::*, recovery::SyncTo};
+    /// let stmt = text::int::<_, _, extra::Err<Rich<char>>>(10)
+    ///     .map(Some)
+    ///     .recover_with(SyncTo::parser(text::keyword("end").ignored(), |_| None))
+    ///     .then_ignore(text::keyword("end").padded())
+    ///     .padded();
+    /// let stmts = stmt.repeated().collect::<Vec<_>>();
+    ///
+    /// // The `e` in `x + e` is not the start of a synchronising keyword
+    /// let (out, errs) = stmts.parse("1 end x + e end 3 end").into_output_errors();
+    /// assert_eq!(out, Some(vec![Some("1"), None, Some("3")]));
+    /// assert_eq!(errs.len(), 1);
+    /// ```
+    pub fn parser(sync: P, fallback: F) -> Self {
+        SyncTo {
+            sync: SyncParser(sync),
+            fallback,
+            nesting: (),
+        }
+    }
+}
+
+impl<S, F, D> SyncTo<S, F, D> {
+    fn sync<'a, M, I, O, E>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        at_sync: impl Fn(&mut InputRef<'a, '_, I, E>) -> bool,
+        is_open: impl Fn(&I::Token) -> bool,
+        is_close: impl Fn(&I::Token) -> bool,
+    ) -> PResult<M, O>
+    where
+        M: Mode,
+        I: ValueInput<'a>,
+        F: Fn(I::Span) -> O,
+        E: ParserExtra<'a, I>,
+    {
+        let alt = inp.errors.alt.take().expect("error but no alt?");
+        let start = inp.offset();
+        let mut depth = 0usize;
+        loop {
+            let before = inp.save();
+            if depth == 0 && at_sync(inp) {
+                inp.rewind(before);
+                break;
+            }
+            inp.rewind(before);
+            match inp.next_inner().1 {
+                Some(tok) if depth == 0 && is_close(&tok) => {
+                    inp.rewind(before);
+                    break;
+                }
+                Some(tok) if is_open(&tok) => depth += 1,
+                Some(tok) if is_close(&tok) => depth -= 1,
+                Some(_) => {}
+                None => {
+                    inp.rewind(before);
+                    break;
+                }
+            }
+        }
+
+        // Nothing was skipped, so there's nothing to recover
+        if inp.offset.into() == start.offset.into() {
+            inp.errors.alt = Some(alt);
+            return Err(());
+        }
+
+        let span = inp.span_since(start);
+        inp.emit(inp.offset, alt.err);
+        Ok(M::bind(|| (self.fallback)(span)))
+    }
+}
+
+impl<S, F, D> SyncTo<SyncTokens<S>, F, D> {
+    fn at_token<'a, I, E>(&self, inp: &mut InputRef<'a, '_, I, E>) -> bool
+    where
+        I: ValueInput<'a>,
+        I::Token: PartialEq,
+        S: Seq<'a, I::Token>,
+        E: ParserExtra<'a, I>,
+    {
+        matches!(inp.next_inner().1, Some(tok) if self.sync.0.contains(&tok))
+    }
+}
+
+impl<S, F> Sealed for SyncTo<S, F> {}
+impl<'a, I, O, E, S, F> Strategy<'a, I, O, E> for SyncTo<SyncTokens<S>, F>
+where
+    I: ValueInput<'a>,
+    I::Token: PartialEq,
+    S: Seq<'a, I::Token>,
+    F: Fn(I::Span) -> O,
+    E: ParserExtra<'a, I>,
+{
+    fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        _parser: &P,
+    ) -> PResult<M, O> {
+        self.sync::<M, _, _, _>(inp, |inp| self.at_token(inp), |_| false, |_| false)
+    }
+}
+
+impl<S, F, Open, Close> Sealed for SyncTo<S, F, (Open, Close)> {}
+impl<'a, I, O, E, S, F, Open, Close> Strategy<'a, I, O, E>
+    for SyncTo<SyncTokens<S>, F, (Open, Close)>
+where
+    I: ValueInput<'a>,
+    I::Token: PartialEq,
+    S: Seq<'a, I::Token>,
+    F: Fn(I::Span) -> O,
+    Open: Seq<'a, I::Token>,
+    Close: Seq<'a, I::Token>,
+    E: ParserExtra<'a, I>,
+{
+    fn recover<M: Mode, P: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        _parser: &P,
+    ) -> PResult<M, O> {
+        let (open, close) = &self.nesting;
+        self.sync::<M, _, _, _>(
+            inp,
+            |inp| self.at_token(inp),
+            |tok| open.contains(tok),
+            |tok| close.contains(tok),
+        )
+    }
+}
+
+impl<'a, I, O, E, P, F> Strategy<'a, I, O, E> for SyncTo<SyncParser<P>, F>
+where
+    I: ValueInput<'a>,
+    P: Parser<'a, I, (), E>,
+    F: Fn(I::Span) -> O,
+    E: ParserExtra<'a, I>,
+{
+    fn recover<M: Mode, A: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        _parser: &A,
+    ) -> PResult<M, O> {
+        self.sync::<M, _, _, _>(
+            inp,
+            |inp| self.sync.0.go::<Check>(inp).is_ok(),
+            |_| false,
+            |_| false,
+        )
+    }
+}
+
+impl<'a, I, O, E, P, F, Open, Close> Strategy<'a, I, O, E>
+    for SyncTo<SyncParser<P>, F, (Open, Close)>
+where
+    I: ValueInput<'a>,
+    I::Token: PartialEq,
+    P: Parser<'a, I, (), E>,
+    F: Fn(I::Span) -> O,
+    Open: Seq<'a, I::Token>,
+    Close: Seq<'a, I::Token>,
+    E: ParserExtra<'a, I>,
+{
+    fn recover<M: Mode, A: Parser<'a, I, O, E>>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+        _parser: &A,
+    ) -> PResult<M, O> {
+        let (open, close) = &self.nesting;
+        self.sync::<M, _, _, _>(
+            inp,
+            |inp| self.sync.0.go::<Check>(inp).is_ok(),
+            |tok| open.contains(tok),
+            |tok| close.contains(tok),
+        )
+    }
+}
+
+/// A 'panic mode' recovery strategy that skips input until one of a set of synchronising tokens is found, generating
+/// a fallback output from the span of the skipped input.
+///
+/// The synchronising token is not consumed, so this strategy works best when applied to a parser that does not itself
+/// include the terminator that follows it: `expr.recover_with(sync_to(";", ...)).then_ignore(just(';'))`, for example.
+/// If the parser fails at a synchronising token then no input is skipped and recovery fails.
+///
+/// By default, nesting is not taken into account. Use [`SyncTo::nested`] to skip over delimited regions of the input,
+/// such that (for example) a `;` within a nested block does not end recovery but an unmatched `}` does.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::prelude::*;
+/// #[derive(Debug, PartialEq)]
+/// enum Stmt<'a> { Assign(&'a str, &'a str), Error(SimpleSpan) }
+///
+/// let assign = text::ident::<_, _, extra::Err<Rich<char>>>()
+///     .then_ignore(just('=').padded())
+///     .then(text::ident())
+///     .map(|(lhs, rhs)| Stmt::Assign(lhs, rhs))
+///     .padded();
+/// let stmt = assign
+///     .recover_with(sync_to(";", Stmt::Error).nested("({", ")}"))
+///     .then_ignore(just(';').padded());
+/// let block = stmt
+///     .repeated()
+///     .collect::<Vec<_>>()
+///     .delimited_by(just('{'), just('}'));
+///
+/// // The `;` within the parentheses does not end recovery
+/// let (out, errs) = block.parse("{ a = b; c = (d; e) + f; g = h; }").into_output_errors();
+/// assert_eq!(out, Some(vec![
+///     Stmt::Assign("a", "b"),
+///     Stmt::Error(SimpleSpan::new(9, 23)),
+///     Stmt::Assign("g", "h"),
+/// ]));
+/// assert_eq!(errs.len(), 1);
+/// ```
+pub fn sync_to<S, F>(sync: S, fallback: F) -> SyncTo<SyncTokens<S>, F> {
+    SyncTo {
+        sync: SyncTokens(sync),
+        fallback,
+        nesting: (),
+    }
+}
+
 /// A recovery parser that searches for a start and end delimiter, respecting nesting.
 ///
 /// It is possible to specify additional delimiter pairs that are valid in the pattern's context for better errors. For
@@ -245,3 +792,150 @@ where
     .delimited_by(just(start), just(end))
     .map_with_span(move |_, span| fallback(span))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::Edit;
+
+    #[test]
+    fn repair_cheapest_insertion() {
+        // Prefer closing a parenthesis to closing a bracket
+        let cost = |edit: &Edit<char>| match edit {
+            Edit::Insert(tok) if **tok == ')' => Some(1),
+            Edit::Insert(_) => Some(2),
+            Edit::Delete(_) => None,
+        };
+        let parser = just::<_, _, extra::Err<Rich<char>>>('(')
+            .ignore_then(text::int(10))
+            .then_ignore(one_of("])").recover_with(repair(|tok| tok, cost)))
+            .then_ignore(end());
+
+        let (out, errs) = parser.parse("(42").into_output_errors();
+        assert_eq!(out, Some("42"));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::new(3, 3));
+        assert_eq!(
+            errs[0].reason(),
+            &crate::error::RichReason::Repaired(Edit::Insert(')'.into())),
+        );
+    }
+
+    #[test]
+    fn repair_deletion() {
+        let cost = |edit: &Edit<char>| match edit {
+            Edit::Delete(tok) if tok.is_ascii_digit() => None,
+            _ => Some(1),
+        };
+        let parser = text::int::<_, _, extra::Err<Rich<char>>>(10)
+            .padded()
+            .then_ignore(just(',').recover_with(repair(|tok| tok, cost)))
+            .repeated()
+            .collect::<Vec<_>>();
+
+        // The stray `;` is deleted, but digits may not be deleted so the missing `,` is inserted instead
+        let (out, errs) = parser.parse("1,2;,3 4,").into_output_errors();
+        assert_eq!(out, Some(vec!["1", "2", "3", "4"]));
+        assert_eq!(
+            errs.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+            vec!["deleted unexpected ';'", "inserted missing ','"],
+        );
+        assert_eq!(errs[0].span(), &SimpleSpan::new(3, 4));
+    }
+
+    #[test]
+    fn sync_to_tokens() {
+        #[derive(Clone, Debug, PartialEq)]
+        enum Token {
+            Num(u32),
+            Plus,
+            Semi,
+            Open,
+            Close,
+        }
+
+        #[derive(Clone, Debug, PartialEq)]
+        enum Expr {
+            Ok,
+            Error(SimpleSpan),
+        }
+
+        let num =
+            any::<&[Token], extra::Err<Rich<Token>>>().filter(|tok| matches!(tok, Token::Num(_)));
+        let expr = num.separated_by(just(Token::Plus)).at_least(1).to(Expr::Ok);
+        let stmt = expr
+            .recover_with(sync_to([Token::Semi], Expr::Error).nested(Token::Open, Token::Close))
+            .then_ignore(just(Token::Semi).or_not());
+        let block = stmt
+            .repeated()
+            .collect::<Vec<_>>()
+            .delimited_by(just(Token::Open), just(Token::Close));
+
+        use Token::*;
+
+        // Recovery skips the `;` within the nested block, and stops at the unmatched `}` of the final statement
+        let (out, errs) = block
+            .parse(&[
+                Open,
+                Num(1),
+                Plus,
+                Num(2),
+                Semi,
+                Plus,
+                Open,
+                Num(3),
+                Semi,
+                Close,
+                Semi,
+                Plus,
+                Num(4),
+                Close,
+            ])
+            .into_output_errors();
+        assert_eq!(errs.len(), 2);
+        assert_eq!(
+            out,
+            Some(vec![
+                Expr::Ok,
+                Expr::Error(SimpleSpan::new(5, 10)),
+                Expr::Error(SimpleSpan::new(11, 13)),
+            ])
+        );
+
+        // Recovery does not skip synchronising tokens
+        let (out, errs) = block.parse(&[Open, Semi, Close]).into_output_errors();
+        assert_eq!(out, None);
+        assert_eq!(errs.len(), 1);
+    }
+
+    #[test]
+    fn recovered_span() {
+        let stmt = text::ident::<_, _, extra::Err<Rich<char>>>()
+            .then_ignore(just(';'))
+            .map(Some)
+            .recover_with(skip_then_retry_until(any().ignored(), just(';').ignored()))
+            .padded()
+            .map_with_recovery(|stmt, info| (stmt, info.recovered(), info.skipped().copied()));
+        let stmts = stmt.repeated().collect::<Vec<_>>();
+
+        let (out, errs) = stmts.parse("a; 42 b; c;").into_output_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(
+            out,
+            Some(vec![
+                (Some("a"), false, None),
+                (Some("b"), true, Some(SimpleSpan::new(3, 8))),
+                (Some("c"), false, None),
+            ]),
+        );
+
+        // Recoveries that are later backtracked out of are forgotten
+        let parser = stmt
+            .then_ignore(just('!'))
+            .ignored()
+            .or(any().repeated())
+            .map_with_recovery(|(), info| info.recovered());
+        assert_eq!(parser.parse("42 b;").into_result(), Ok(false));
+    }
+}
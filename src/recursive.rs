@@ -11,7 +11,7 @@
 use super::*;
 
 #[cfg(not(feature = "sync"))]
-struct OnceCell<T>(core::cell::Cell<Option<T>>);
+pub(crate) struct OnceCell<T>(core::cell::Cell<Option<T>>);
 #[cfg(not(feature = "sync"))]
 impl<T> OnceCell<T> {
     pub fn new() -> Self {
@@ -35,10 +35,18 @@ impl<T> OnceCell<T> {
         // neither function is possibly reentrant so there's no way we can invalidate mut xor shared aliasing
         unsafe { (&*self.0.as_ptr()).as_ref() }
     }
+    #[inline]
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        if self.get().is_none() {
+            // If `f` initialised the cell itself, just keep the first value
+            let _ = self.set(f());
+        }
+        self.get().unwrap()
+    }
 }
 
 #[cfg(feature = "sync")]
-struct OnceCell<T>(spin::once::Once<T>);
+pub(crate) struct OnceCell<T>(spin::once::Once<T>);
 #[cfg(feature = "sync")]
 impl<T> OnceCell<T> {
     pub fn new() -> Self {
@@ -58,6 +66,10 @@ impl<T> OnceCell<T> {
     pub fn get(&self) -> Option<&T> {
         self.0.get()
     }
+    #[inline]
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        self.0.call_once(f)
+    }
 }
 
 // TODO: Ensure that this doesn't produce leaks
@@ -182,6 +194,24 @@ fn recurse<R, F: FnOnce() -> R>(f: F) -> R {
     f()
 }
 
+impl<'a, 'b, I: Input<'a>, O, E: ParserExtra<'a, I>> fmt::Debug
+    for Recursive<Indirect<'a, 'b, I, O, E>>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Showing the definition would recurse forever, so show its name instead
+        match self.name() {
+            Some(name) => f
+                .debug_tuple("Recursive")
+                .field(&format_args!("{}", name))
+                .finish(),
+            None => f
+                .debug_tuple("Recursive")
+                .field(&format_args!(".."))
+                .finish(),
+        }
+    }
+}
+
 impl<'a, 'b, I, O, E> ParserSealed<'a, I, O, E> for Recursive<Indirect<'a, 'b, I, O, E>>
 where
     I: Input<'a>,
@@ -201,9 +231,58 @@ where
         })
     }
 
+    fn name(&self) -> Option<&'static str> {
+        self.parser().inner.get()?.name()
+    }
+
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let parser = self.parser();
+        cx.recursive(RefC::as_ptr(&parser) as *const () as usize, |cx| {
+            parser
+                .inner
+                .get()
+                .expect("Recursive parser used before being defined")
+                .summarize(cx)
+        })
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        gen.nested(|gen| {
+            self.parser()
+                .inner
+                .get()
+                .expect("Recursive parser used before being defined")
+                .generate(gen)
+        })
+    }
+
     go_extra!(O);
 }
 
+impl<'a, 'b, I: Input<'a>, O, E: ParserExtra<'a, I>> fmt::Debug
+    for Recursive<Direct<'a, 'b, I, O, E>>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name() {
+            Some(name) => f
+                .debug_tuple("Recursive")
+                .field(&format_args!("{}", name))
+                .finish(),
+            None => f
+                .debug_tuple("Recursive")
+                .field(&format_args!(".."))
+                .finish(),
+        }
+    }
+}
+
 impl<'a, 'b, I, O, E> ParserSealed<'a, I, O, E> for Recursive<Direct<'a, 'b, I, O, E>>
 where
     I: Input<'a>,
@@ -214,6 +293,28 @@ where
         recurse(move || M::invoke(&*self.parser(), inp))
     }
 
+    fn name(&self) -> Option<&'static str> {
+        self.parser().name()
+    }
+
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let parser = self.parser();
+        cx.recursive(RefC::as_ptr(&parser) as *const () as usize, |cx| {
+            parser.summarize(cx)
+        })
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        gen.nested(|gen| self.parser().generate(gen))
+    }
+
     go_extra!(O);
 }
 
@@ -2,45 +2,334 @@
 
 use super::*;
 
-/// See [`regex()`].
+use crate::{input::StrInput, recursive::OnceCell};
+use regex_automata::{
+    dfa::{dense, Automaton},
+    util::start,
+    Anchored,
+};
+
+/// See [`regex()`] and [`try_regex`].
 pub struct Regex<C: Char, I, E> {
     regex: C::Regex,
+    // Built on first use by inputs that can't be sliced
+    dfa: OnceCell<dense::DFA<Vec<u32>>>,
     phantom: PhantomData<(E, I)>,
 }
 
-/// Match input based on a provided regex pattern
+impl<C: Char, I, E> fmt::Debug for Regex<C, I, E>
+where
+    C::Regex: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Regex").field(&self.regex).finish()
+    }
+}
+
+/// Match input based on a provided regex pattern.
+///
+/// The output of this parser is the text that matched the pattern (see [`TextInput::Text`]). To get the slices
+/// matched by capture groups, see [`regex_captures`].
+///
+/// The pattern is matched directly against inputs that implement [`StrInput`]. Other inputs, such as a [`Stream`] of
+/// [`char`]s, are matched token-by-token, yielding owned text.
+///
+/// [`Stream`]: crate::input::Stream
+///
+/// # Panics
+///
+/// Panics if the pattern is not a valid regex. To handle invalid patterns, see [`try_regex`].
+///
+/// When matching against an input that does not implement [`StrInput`], panics on first use if the pattern cannot be
+/// matched token-by-token. This is the case for patterns containing Unicode word boundaries (`\b`); use ASCII word
+/// boundaries (`(?-u:\b)`) instead.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, input::Stream};
+/// let float = regex::<_, &str, extra::Default>(r"[0-9]+\.[0-9]*");
+/// assert_eq!(float.parse("3.14").into_result(), Ok("3.14"));
+///
+/// // Inputs that can't be sliced yield owned strings
+/// let float = regex::<_, _, extra::Default>(r"[0-9]+\.[0-9]*");
+/// assert_eq!(
+///     float.parse(Stream::from_iter("2.5".chars())).into_result(),
+///     Ok(String::from("2.5")),
+/// );
+/// ```
 pub fn regex<C: Char, I, E>(pattern: &str) -> Regex<C, I, E> {
-    Regex {
-        regex: C::new_regex(pattern),
+    try_regex(pattern).unwrap_or_else(|err| panic!("Failed to compile regex: {}", err))
+}
+
+/// Like [`regex()`], but returns an error if the pattern is not a valid regex instead of panicking.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, regex::try_regex};
+/// assert!(try_regex::<char, &str, extra::Default>("[a-z]+").is_ok());
+/// assert!(try_regex::<char, &str, extra::Default>("[a-z").is_err());
+/// ```
+pub fn try_regex<C: Char, I, E>(pattern: &str) -> Result<Regex<C, I, E>, ::regex::Error> {
+    Ok(Regex {
+        regex: C::new_regex(pattern)?,
+        dfa: OnceCell::new(),
         phantom: PhantomData,
+    })
+}
+
+impl<C: Char, I, E> Regex<C, I, E> {
+    /// Yield the slices matched by each of the pattern's capture groups instead of the whole match. See
+    /// [`regex_captures`].
+    pub fn captures(self) -> RegexCaptures<C, I, E> {
+        RegexCaptures {
+            names: C::regex_capture_names(&self.regex).into(),
+            regex: self.regex,
+            phantom: PhantomData,
+        }
     }
 }
 
-impl<'a, C, I, E> ParserSealed<'a, I, &'a C::Str, E> for Regex<C, I, E>
+impl<C: Char, I, E> Regex<C, I, E> {
+    // Match the pattern against the input one token at a time, leaving the input after the longest match (according
+    // to the pattern's leftmost-first semantics) if there is one
+    fn match_tokens<'a>(&self, inp: &mut InputRef<'a, '_, I, E>) -> bool
+    where
+        I: TextInput<'a, C>,
+        E: ParserExtra<'a, I>,
+    {
+        let dfa = self.dfa.get_or_init(|| {
+            let pattern = C::regex_pattern(&self.regex);
+            C::new_regex_dfa(pattern).unwrap_or_else(|err| {
+                panic!(
+                    "Failed to match regex {:?} token-by-token: {}",
+                    pattern, err
+                )
+            })
+        });
+        let mut state = dfa
+            .start_state(&start::Config::new().anchored(Anchored::Yes))
+            .expect("regex DFA has no anchored start state");
+
+        let before = inp.save();
+        let mut end = None;
+        let mut buf = [0; 4];
+        'tokens: loop {
+            let boundary = inp.save();
+            match inp.next_inner().1 {
+                Some(c) => {
+                    for (i, byte) in c.encode_bytes(&mut buf).iter().enumerate() {
+                        state = dfa.next_state(state, *byte);
+                        // Matches are reported one byte late, so a match here ends before the current token
+                        if dfa.is_match_state(state) && i == 0 {
+                            end = Some(boundary);
+                        } else if dfa.is_dead_state(state) || dfa.is_quit_state(state) {
+                            break 'tokens;
+                        }
+                    }
+                }
+                None => {
+                    if dfa.is_match_state(dfa.next_eoi_state(state)) {
+                        end = Some(boundary);
+                    }
+                    break;
+                }
+            }
+        }
+
+        inp.rewind(end.unwrap_or(before));
+        end.is_some()
+    }
+}
+
+impl<'a, C, I, E> ParserSealed<'a, I, I::Text, E> for Regex<C, I, E>
+where
+    C: Char,
+    I: TextInput<'a, C>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, I::Text> {
+        let before = inp.offset();
+        let matched = match inp.trailing_str() {
+            Some(trailing) => C::match_regex(&self.regex, trailing)
+                .map(|len| inp.skip_bytes(len))
+                .is_some(),
+            None => self.match_tokens(inp),
+        };
+        if matched {
+            let after = inp.offset();
+            Ok(M::bind(|| inp.text_inner(before.offset..after.offset)))
+        } else {
+            report_mismatch::<C, _, _>(&self.regex, inp, before);
+            Err(())
+        }
+    }
+
+    go_extra!(I::Text);
+}
+
+/// See [`regex_captures`] and [`Regex::captures`].
+pub struct RegexCaptures<C: Char, I, E> {
+    regex: C::Regex,
+    names: RefC<[Option<String>]>,
+    phantom: PhantomData<(E, I)>,
+}
+
+impl<C: Char, I, E> fmt::Debug for RegexCaptures<C, I, E>
+where
+    C::Regex: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("RegexCaptures").field(&self.regex).finish()
+    }
+}
+
+/// Match input based on a provided regex pattern, yielding the slices of the input matched by each of its capture
+/// groups.
+///
+/// The output of this parser is [`Captures`]. As is conventional for regexes, group `0` is the whole match.
+///
+/// Unlike [`regex()`], this parser only supports inputs that implement [`StrInput`].
+///
+/// # Panics
+///
+/// Panics if the pattern is not a valid regex. To handle invalid patterns, use [`try_regex`] followed by
+/// [`Regex::captures`].
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, regex::regex_captures};
+/// let version = regex_captures::<_, _, extra::Default>(r"(?P<major>\d+)\.(?P<minor>\d+)(-(\w+))?");
+///
+/// let caps = version.parse("1.42").into_result().unwrap();
+/// assert_eq!(caps.get(0), Some("1.42"));
+/// assert_eq!(caps.name("major"), Some("1"));
+/// assert_eq!(caps.name("minor"), Some("42"));
+/// // Groups that did not participate in the match are `None`
+/// assert_eq!(caps.get(4), None);
+///
+/// let caps = version.parse("2.0-beta").into_result().unwrap();
+/// assert_eq!(caps.get(4), Some("beta"));
+/// ```
+pub fn regex_captures<C: Char, I, E>(pattern: &str) -> RegexCaptures<C, I, E> {
+    regex(pattern).captures()
+}
+
+impl<'a, C, I, E> ParserSealed<'a, I, Captures<'a, C::Str>, E> for RegexCaptures<C, I, E>
 where
     C: Char,
     I: StrInput<'a, C>,
     E: ParserExtra<'a, I>,
 {
     #[inline]
-    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, &'a C::Str> {
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, Captures<'a, C::Str>> {
         let before = inp.offset();
-        match C::match_regex(&self.regex, inp.slice_trailing_inner()) {
-            Some(len) => {
-                let before = inp.offset();
+        match C::captures_regex(&self.regex, inp.slice_trailing_inner()) {
+            Some(groups) => {
+                // Group 0 is always the whole match
+                let len = groups[0].as_ref().map_or(0, |range| range.end);
                 inp.skip_bytes(len);
-                let after = inp.offset();
-                Ok(M::bind(|| inp.slice_inner(before.offset..after.offset)))
+                Ok(M::bind(|| Captures {
+                    groups: groups
+                        .into_iter()
+                        .map(|range| {
+                            range.map(|range| {
+                                inp.slice_inner(
+                                    before.offset + range.start..before.offset + range.end,
+                                )
+                            })
+                        })
+                        .collect(),
+                    names: self.names.clone(),
+                }))
             }
             None => {
-                // TODO: Improve error
-                inp.add_alt(inp.offset().offset, None, None, inp.span_since(before));
+                report_mismatch::<C, _, _>(&self.regex, inp, before);
                 Err(())
             }
         }
     }
 
-    go_extra!(&'a C::Str);
+    go_extra!(Captures<'a, C::Str>);
+}
+
+// Report that the input at the current position doesn't match the given regex
+fn report_mismatch<'a, 'parse, C, I, E>(
+    regex: &C::Regex,
+    inp: &mut InputRef<'a, 'parse, I, E>,
+    before: input::Offset<'a, 'parse, I>,
+) where
+    C: Char,
+    I: ValueInput<'a, Token = C>,
+    E: ParserExtra<'a, I>,
+{
+    let (at, found) = inp.next_maybe_inner();
+    let err = E::Error::expected_regex(
+        C::regex_pattern(regex),
+        found.map(|f| f.into()),
+        inp.span_since(before),
+    );
+    inp.add_alt_err(at, err);
+}
+
+/// The slices of the input matched by the capture groups of a regex. See [`regex_captures`].
+pub struct Captures<'a, S: ?Sized> {
+    groups: Vec<Option<&'a S>>,
+    names: RefC<[Option<String>]>,
+}
+
+impl<'a, S: ?Sized> Captures<'a, S> {
+    /// Get the slice matched by the capture group with the given index, if it participated in the match.
+    ///
+    /// Group `0` is the whole match.
+    pub fn get(&self, idx: usize) -> Option<&'a S> {
+        self.groups.get(idx).copied().flatten()
+    }
+
+    /// Get the slice matched by the capture group with the given name, if it participated in the match.
+    pub fn name(&self, name: &str) -> Option<&'a S> {
+        let idx = self.names.iter().position(|n| n.as_deref() == Some(name))?;
+        self.get(idx)
+    }
+
+    /// Get the number of capture groups in the pattern, including group `0`.
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Returns `true` if the pattern has no capture groups. This is never the case, since group `0` always exists.
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Iterate over the slices matched by each capture group, in order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Option<&'a S>> + '_ {
+        self.groups.iter().copied()
+    }
+}
+
+impl<'a, S: ?Sized> Clone for Captures<'a, S> {
+    fn clone(&self) -> Self {
+        Self {
+            groups: self.groups.clone(),
+            names: self.names.clone(),
+        }
+    }
+}
+
+impl<'a, S: ?Sized + fmt::Debug> fmt::Debug for Captures<'a, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, S: ?Sized + PartialEq> PartialEq for Captures<'a, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.groups == other.groups
+    }
 }
 
 #[cfg(test)]
@@ -75,4 +364,84 @@ mod tests {
             ]),
         );
     }
+
+    #[test]
+    fn regex_errors() {
+        use self::error::{Rich, RichPattern};
+        use self::prelude::*;
+        use self::regex::*;
+
+        let ident = regex::<_, _, extra::Err<Rich<char>>>("[a-z]+");
+        let parser = ident.or(just('0').to("zero"));
+
+        let errs = parser.parse("?").into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(0..1));
+        assert_eq!(errs[0].found(), Some(&'?'));
+        assert_eq!(
+            errs[0].expected().collect::<Vec<_>>(),
+            [
+                &RichPattern::Regex("[a-z]+".into()),
+                &RichPattern::Token('0'.into())
+            ],
+        );
+        assert_eq!(errs[0].to_string(), "found '?' expected /[a-z]+/, or '0'");
+
+        assert!(try_regex::<char, &str, extra::Default>("(unclosed").is_err());
+    }
+
+    #[test]
+    fn regex_captures_bytes() {
+        use self::prelude::*;
+        use self::regex::*;
+
+        let pair = regex_captures::<_, _, extra::Default>(r"(?P<key>\w+)=(?P<value>\w*)");
+        let pairs = pair.separated_by(just(b',')).collect::<Vec<_>>();
+
+        let caps = pairs.parse(b"a=1,bc=" as &[u8]).into_result().unwrap();
+        assert_eq!(caps.len(), 2);
+        assert_eq!(caps[0].name("key"), Some(b"a" as &[u8]));
+        assert_eq!(caps[0].name("value"), Some(b"1" as &[u8]));
+        assert_eq!(caps[1].get(0), Some(b"bc=" as &[u8]));
+        assert_eq!(caps[1].name("value"), Some(b"" as &[u8]));
+        assert_eq!(caps[1].name("missing"), None);
+    }
+
+    #[test]
+    fn regex_stream() {
+        use self::error::Rich;
+        use self::input::Stream;
+        use self::prelude::*;
+        use self::regex::*;
+
+        fn parse<'a, C: Char, I: TextInput<'a, C>>(pattern: &str, input: I) -> Option<I::Text> {
+            regex::<_, _, extra::Default>(pattern)
+                .lazy()
+                .parse(input)
+                .into_output()
+        }
+
+        let chars = |s: &'static str| Stream::from_iter(s.chars());
+        assert_eq!(parse(r"a+", chars("aaab")).as_deref(), Some("aaa"));
+        // Leftmost-first semantics, like a `&str` input
+        assert_eq!(parse(r"a|ab", chars("ab")).as_deref(), Some("a"));
+        assert_eq!(parse(r"ab|a", chars("ab")).as_deref(), Some("ab"));
+        assert_eq!(parse(r"a*", chars("bbb")).as_deref(), Some(""));
+        assert_eq!(parse(r"[αβ]+$", chars("αβα")).as_deref(), Some("αβα"));
+        assert_eq!(parse(r"a+$", chars("aab")), None);
+        assert_eq!(parse(r"(?-u:\b)x", chars("x")).as_deref(), Some("x"));
+
+        let bytes = |s: &'static [u8]| Stream::from_iter(s.iter().copied());
+        assert_eq!(
+            parse(r"(?-u)[\x80-\xff]+", bytes(b"\xce\xb1z")).as_deref(),
+            Some(b"\xce\xb1" as &[u8]),
+        );
+
+        // Errors are the same as for `&str` inputs
+        let ident = regex::<_, _, extra::Err<Rich<char>>>("[a-z]+");
+        let errs = ident.parse(chars("?")).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(0..1));
+        assert_eq!(errs[0].to_string(), "found '?' expected /[a-z]+/");
+    }
 }
@@ -130,6 +130,20 @@ where
     }
 }
 
+impl<'a, C: Char, I: Iterator<Item = C> + 'a> TextInput<'a, C> for Stream<I> {
+    type Text = C::Owned;
+
+    #[inline]
+    unsafe fn text(&self, range: Range<Self::Offset>) -> Self::Text {
+        let mut other = Cell::new((Vec::new(), None));
+        self.tokens.swap(&other);
+        // Every offset up to `range.end` has already been pulled from the iterator
+        let text = other.get_mut().0[range].iter().copied().collect();
+        self.tokens.swap(&other);
+        text
+    }
+}
+
 #[test]
 fn spanned() {
     fn parser<'a>() -> impl Parser<
@@ -20,16 +20,48 @@ pub trait Char: Sized + Copy + PartialEq + Sealed + 'static {
     /// For [`char`], this is [`str`]. For [`u8`], this is [`[u8]`].
     type Str: ?Sized + 'static;
 
+    /// The owned version of [`Char::Str`], produced by text parsers when the input cannot be sliced (see
+    /// [`TextInput`]).
+    ///
+    /// For [`char`], this is [`String`]. For [`u8`], this is [`Vec<u8>`].
+    type Owned: Borrow<Self::Str> + FromIterator<Self> + 'static;
+
     /// The type of a regex expression which can match on this type
     #[cfg(feature = "regex")]
     type Regex;
 
     #[cfg(feature = "regex")]
     #[doc(hidden)]
-    fn new_regex(pattern: &str) -> Self::Regex;
+    fn new_regex(pattern: &str) -> Result<Self::Regex, ::regex::Error>;
+    #[cfg(feature = "regex")]
+    #[doc(hidden)]
+    fn regex_pattern(regex: &Self::Regex) -> &str;
+    #[cfg(feature = "regex")]
+    #[doc(hidden)]
+    fn regex_capture_names(regex: &Self::Regex) -> Vec<Option<String>>;
     #[cfg(feature = "regex")]
     #[doc(hidden)]
     fn match_regex(regex: &Self::Regex, trailing: &Self::Str) -> Option<usize>;
+    #[cfg(feature = "regex")]
+    #[doc(hidden)]
+    fn captures_regex(
+        regex: &Self::Regex,
+        trailing: &Self::Str,
+    ) -> Option<Vec<Option<Range<usize>>>>;
+    #[cfg(feature = "regex")]
+    #[doc(hidden)]
+    fn new_regex_dfa(
+        pattern: &str,
+    ) -> Result<
+        regex_automata::dfa::dense::DFA<Vec<u32>>,
+        Box<regex_automata::dfa::dense::BuildError>,
+    >;
+    #[cfg(feature = "regex")]
+    #[doc(hidden)]
+    fn encode_bytes(self, buf: &mut [u8; 4]) -> &[u8];
+
+    #[doc(hidden)]
+    fn str_first(s: &Self::Str) -> Option<Self>;
 
     /// Convert the given ASCII character to this character type.
     fn from_ascii(c: u8) -> Self;
@@ -54,12 +86,25 @@ impl Sealed for char {}
 impl Char for char {
     type Str = str;
 
+    type Owned = String;
+
     #[cfg(feature = "regex")]
     type Regex = ::regex::Regex;
 
     #[cfg(feature = "regex")]
-    fn new_regex(pattern: &str) -> Self::Regex {
-        ::regex::Regex::new(pattern).expect("Failed to compile regex")
+    fn new_regex(pattern: &str) -> Result<Self::Regex, ::regex::Error> {
+        ::regex::Regex::new(pattern)
+    }
+    #[cfg(feature = "regex")]
+    fn regex_pattern(regex: &Self::Regex) -> &str {
+        regex.as_str()
+    }
+    #[cfg(feature = "regex")]
+    fn regex_capture_names(regex: &Self::Regex) -> Vec<Option<String>> {
+        regex
+            .capture_names()
+            .map(|name| name.map(String::from))
+            .collect()
     }
     #[cfg(feature = "regex")]
     #[inline]
@@ -69,6 +114,36 @@ impl Char for char {
             .filter(|m| m.start() == 0)
             .map(|m| m.end())
     }
+    #[cfg(feature = "regex")]
+    #[inline]
+    fn captures_regex(
+        regex: &Self::Regex,
+        trailing: &Self::Str,
+    ) -> Option<Vec<Option<Range<usize>>>> {
+        regex
+            .captures(trailing)
+            .filter(|caps| caps.get(0).map(|m| m.start()) == Some(0))
+            .map(|caps| caps.iter().map(|m| m.map(|m| m.range())).collect())
+    }
+
+    #[cfg(feature = "regex")]
+    fn new_regex_dfa(
+        pattern: &str,
+    ) -> Result<
+        regex_automata::dfa::dense::DFA<Vec<u32>>,
+        Box<regex_automata::dfa::dense::BuildError>,
+    > {
+        new_regex_dfa(pattern, true)
+    }
+    #[cfg(feature = "regex")]
+    #[inline]
+    fn encode_bytes(self, buf: &mut [u8; 4]) -> &[u8] {
+        self.encode_utf8(buf).as_bytes()
+    }
+
+    fn str_first(s: &Self::Str) -> Option<Self> {
+        s.chars().next()
+    }
 
     fn from_ascii(c: u8) -> Self {
         c as char
@@ -94,12 +169,25 @@ impl Sealed for u8 {}
 impl Char for u8 {
     type Str = [u8];
 
+    type Owned = Vec<u8>;
+
     #[cfg(feature = "regex")]
     type Regex = ::regex::bytes::Regex;
 
     #[cfg(feature = "regex")]
-    fn new_regex(pattern: &str) -> Self::Regex {
-        ::regex::bytes::Regex::new(pattern).expect("Failed to compile regex")
+    fn new_regex(pattern: &str) -> Result<Self::Regex, ::regex::Error> {
+        ::regex::bytes::Regex::new(pattern)
+    }
+    #[cfg(feature = "regex")]
+    fn regex_pattern(regex: &Self::Regex) -> &str {
+        regex.as_str()
+    }
+    #[cfg(feature = "regex")]
+    fn regex_capture_names(regex: &Self::Regex) -> Vec<Option<String>> {
+        regex
+            .capture_names()
+            .map(|name| name.map(String::from))
+            .collect()
     }
     #[cfg(feature = "regex")]
     #[inline]
@@ -109,6 +197,37 @@ impl Char for u8 {
             .filter(|m| m.start() == 0)
             .map(|m| m.end())
     }
+    #[cfg(feature = "regex")]
+    #[inline]
+    fn captures_regex(
+        regex: &Self::Regex,
+        trailing: &Self::Str,
+    ) -> Option<Vec<Option<Range<usize>>>> {
+        regex
+            .captures(trailing)
+            .filter(|caps| caps.get(0).map(|m| m.start()) == Some(0))
+            .map(|caps| caps.iter().map(|m| m.map(|m| m.range())).collect())
+    }
+
+    #[cfg(feature = "regex")]
+    fn new_regex_dfa(
+        pattern: &str,
+    ) -> Result<
+        regex_automata::dfa::dense::DFA<Vec<u32>>,
+        Box<regex_automata::dfa::dense::BuildError>,
+    > {
+        new_regex_dfa(pattern, false)
+    }
+    #[cfg(feature = "regex")]
+    #[inline]
+    fn encode_bytes(self, buf: &mut [u8; 4]) -> &[u8] {
+        buf[0] = self;
+        &buf[..1]
+    }
+
+    fn str_first(s: &Self::Str) -> Option<Self> {
+        s.first().copied()
+    }
 
     fn from_ascii(c: u8) -> Self {
         c
@@ -130,12 +249,103 @@ impl Char for u8 {
     }
 }
 
+// Build an anchored DFA, used to match regexes token-by-token on inputs that can't be sliced
+#[cfg(feature = "regex")]
+fn new_regex_dfa(
+    pattern: &str,
+    utf8: bool,
+) -> Result<regex_automata::dfa::dense::DFA<Vec<u32>>, Box<regex_automata::dfa::dense::BuildError>>
+{
+    use regex_automata::{dfa::dense, nfa::thompson, util::syntax, MatchKind};
+
+    dense::Builder::new()
+        .configure(
+            dense::Config::new()
+                .match_kind(MatchKind::LeftmostFirst)
+                .start_kind(regex_automata::dfa::StartKind::Anchored),
+        )
+        .syntax(syntax::Config::new().utf8(utf8))
+        .thompson(thompson::Config::new().utf8(utf8))
+        .build(pattern)
+        .map_err(Box::new)
+}
+
+// Like `Slice`, but outputs the `TextInput::Text` matched by the inner parser
+struct Text<A, C, O> {
+    parser: A,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(C, O)>,
+}
+
+impl<A: Copy, C, O> Copy for Text<A, C, O> {}
+impl<A: Clone, C, O> Clone for Text<A, C, O> {
+    fn clone(&self) -> Self {
+        Text {
+            parser: self.parser.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<'a, A, C, I, O, E> ParserSealed<'a, I, I::Text, E> for Text<A, C, O>
+where
+    A: Parser<'a, I, O, E>,
+    C: Char,
+    I: TextInput<'a, C>,
+    E: ParserExtra<'a, I>,
+{
+    #[inline(always)]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, I::Text> {
+        let before = inp.offset().offset;
+        self.parser.go::<Check>(inp)?;
+        let after = inp.offset().offset;
+
+        Ok(M::bind(|| inp.text_inner(before..after)))
+    }
+
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        self.parser.summarize(cx)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        self.parser.generate(gen)
+    }
+
+    go_extra!(I::Text);
+}
+
+fn text<'a, A, C, I, O, E>(parser: A) -> Text<A, C, O>
+where
+    A: Parser<'a, I, O, E>,
+    C: Char,
+    I: TextInput<'a, C>,
+    E: ParserExtra<'a, I>,
+{
+    Text {
+        parser,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
 /// A parser that accepts (and ignores) any number of whitespace characters before or after another pattern.
 #[derive(Copy, Clone)]
 pub struct Padded<A> {
     pub(crate) parser: A,
 }
 
+impl<A: fmt::Debug> fmt::Debug for Padded<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Padded").field(&self.parser).finish()
+    }
+}
+
 impl<'a, I, O, E, A> ParserSealed<'a, I, O, E> for Padded<A>
 where
     I: ValueInput<'a>,
@@ -150,6 +360,30 @@ where
         Ok(out)
     }
 
+    fn summarize(&self, cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        let whitespace = Summary::token(None).repeated(0);
+        let inner = self.parser.summarize(cx);
+        whitespace.clone().then(inner).then(whitespace)
+    }
+
+    #[cfg(feature = "arbitrary")]
+    fn generate(&self, gen: &mut Generator<'_, I::Token>) -> Result<(), GenerateError>
+    where
+        I::Token: Clone,
+    {
+        for _ in 0..gen.repeats(0, 1)? {
+            gen.push(I::Token::from_ascii(b' '));
+        }
+        self.parser.generate(gen)?;
+        for _ in 0..gen.repeats(0, 1)? {
+            gen.push(I::Token::from_ascii(b' '));
+        }
+        Ok(())
+    }
+
     go_extra!(O);
 }
 
@@ -170,7 +404,7 @@ where
 /// // ...including none at all!
 /// assert_eq!(whitespace.parse("").into_result(), Ok(()));
 /// ```
-pub fn whitespace<'a, C: Char, I: ValueInput<'a> + StrInput<'a, C>, E: ParserExtra<'a, I>>(
+pub fn whitespace<'a, C: Char, I: ValueInput<'a, Token = C>, E: ParserExtra<'a, I>>(
 ) -> Repeated<impl Parser<'a, I, (), E> + Copy + Clone, (), I, E>
 where
     I::Token: Char,
@@ -200,7 +434,7 @@ where
 /// // ... but not newlines
 /// assert!(inline_whitespace.at_least(1).parse("\n\r").has_errors());
 /// ```
-pub fn inline_whitespace<'a, C: Char, I: ValueInput<'a> + StrInput<'a, C>, E: ParserExtra<'a, I>>(
+pub fn inline_whitespace<'a, C: Char, I: ValueInput<'a, Token = C>, E: ParserExtra<'a, I>>(
 ) -> Repeated<impl Parser<'a, I, (), E> + Copy + Clone, (), I, E>
 where
     I::Token: Char,
@@ -303,8 +537,8 @@ where
 /// An integer is defined as a non-empty sequence of ASCII digits, where the first digit is non-zero or the sequence
 /// has length one.
 ///
-/// The output type of this parser is `I::Slice` (i.e: [`&str`] when `I` is [`&str`], and [`&[u8]`]
-/// when `I::Slice` is [`&[u8]`]).
+/// The output type of this parser is [`TextInput::Text`] (i.e: [`&str`] when `I` is [`&str`], [`&[u8]`] when `I` is
+/// [`&[u8]`], and an owned [`String`] when `I` is a [`input::Stream`] of [`char`]s).
 ///
 /// The `radix` parameter functions identically to [`char::is_digit`]. If in doubt, choose `10`.
 ///
@@ -329,44 +563,46 @@ where
 /// ```
 ///
 #[must_use]
-pub fn int<'a, I: ValueInput<'a> + StrInput<'a, C>, C: Char, E: ParserExtra<'a, I>>(
+pub fn int<'a, I: TextInput<'a, C>, C: Char, E: ParserExtra<'a, I>>(
     radix: u32,
-) -> impl Parser<'a, I, &'a C::Str, E> + Copy + Clone {
-    any()
-        .filter(move |c: &C| c.is_digit(radix) && c != &C::digit_zero())
-        .map(Some)
-        .then(any().filter(move |c: &C| c.is_digit(radix)).repeated())
-        .ignored()
-        .or(just(C::digit_zero()).ignored())
-        .slice()
+) -> impl Parser<'a, I, I::Text, E> + Copy + Clone {
+    text(
+        any()
+            .filter(move |c: &C| c.is_digit(radix) && c != &C::digit_zero())
+            .map(Some)
+            .then(any().filter(move |c: &C| c.is_digit(radix)).repeated())
+            .ignored()
+            .or(just(C::digit_zero()).ignored()),
+    )
 }
 
 /// A parser that accepts a C-style identifier.
 ///
-/// The output type of this parser is [`Char::Str`] (i.e: [`&str`] when `C` is [`char`], and [`&[u8]`] when `C` is
-/// [`u8`]).
+/// The output type of this parser is [`TextInput::Text`] (i.e: [`&str`] when `I` is [`&str`], [`&[u8]`] when `I` is
+/// [`&[u8]`], and an owned [`String`] when `I` is a [`input::Stream`] of [`char`]s).
 ///
 /// An identifier is defined as an ASCII alphabetic character or an underscore followed by any number of alphanumeric
 /// characters or underscores. The regex pattern for it is `[a-zA-Z_][a-zA-Z0-9_]*`.
 #[must_use]
-pub fn ident<'a, I: ValueInput<'a> + StrInput<'a, C>, C: Char, E: ParserExtra<'a, I>>(
-) -> impl Parser<'a, I, &'a C::Str, E> + Copy + Clone {
-    any()
-        .filter(|c: &C| c.to_char().is_ascii_alphabetic() || c.to_char() == '_')
-        .then(
-            any()
-                .filter(|c: &C| c.to_char().is_ascii_alphanumeric() || c.to_char() == '_')
-                .repeated(),
-        )
-        .slice()
+pub fn ident<'a, I: TextInput<'a, C>, C: Char, E: ParserExtra<'a, I>>(
+) -> impl Parser<'a, I, I::Text, E> + Copy + Clone {
+    text(
+        any()
+            .filter(|c: &C| c.to_char().is_ascii_alphabetic() || c.to_char() == '_')
+            .then(
+                any()
+                    .filter(|c: &C| c.to_char().is_ascii_alphanumeric() || c.to_char() == '_')
+                    .repeated(),
+            ),
+    )
 }
 
 // TODO: Better native form of semantic indentation that uses the context system?
 
 /// Like [`ident`], but only accepts a specific identifier while rejecting trailing identifier characters.
 ///
-/// The output type of this parser is `I::Slice` (i.e: [`&str`] when `I` is [`&str`], and [`&[u8]`]
-/// when `I::Slice` is [`&[u8]`]).
+/// The output type of this parser is [`TextInput::Text`] (i.e: [`&str`] when `I` is [`&str`], [`&[u8]`] when `I` is
+/// [`&[u8]`], and an owned [`String`] when `I` is a [`input::Stream`] of [`char`]s).
 ///
 /// # Examples
 ///
@@ -384,24 +620,79 @@ pub fn ident<'a, I: ValueInput<'a> + StrInput<'a, C>, C: Char, E: ParserExtra<'a
 /// ```
 pub fn keyword<
     'a,
-    I: ValueInput<'a> + StrInput<'a, C>,
+    I: TextInput<'a, C>,
     C: Char + 'a,
     Str: AsRef<C::Str> + 'a + Clone,
     E: ParserExtra<'a, I> + 'a,
 >(
     keyword: Str,
-) -> impl Parser<'a, I, &'a C::Str, E> + Clone + 'a
+) -> Keyword<I, C, Str, E>
 where
     C::Str: PartialEq,
 {
-    // TODO: use .filter(...), improve error messages
-    ident()
-        .try_map(move |s: &C::Str, span| {
-            if s == keyword.as_ref() {
-                Ok(())
-            } else {
-                Err(E::Error::expected_found(None, None, span))
-            }
-        })
-        .slice()
+    Keyword {
+        keyword,
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+/// See [`keyword`].
+pub struct Keyword<I, C, Str, E> {
+    keyword: Str,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(I, C, E)>,
+}
+
+impl<I, C, Str: Copy, E> Copy for Keyword<I, C, Str, E> {}
+impl<I, C, Str: Clone, E> Clone for Keyword<I, C, Str, E> {
+    fn clone(&self) -> Self {
+        Keyword {
+            keyword: self.keyword.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<I, C, Str: fmt::Debug, E> fmt::Debug for Keyword<I, C, Str, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Keyword").field(&self.keyword).finish()
+    }
+}
+
+impl<'a, I, C, Str, E> ParserSealed<'a, I, I::Text, E> for Keyword<I, C, Str, E>
+where
+    I: TextInput<'a, C>,
+    C: Char,
+    Str: AsRef<C::Str>,
+    C::Str: PartialEq,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(&self, inp: &mut InputRef<'a, '_, I, E>) -> PResult<M, I::Text> {
+        let before = inp.offset();
+        let s = ident::<I, C, E>().go::<Emit>(inp)?;
+        // TODO: improve error messages
+        if s.borrow() == self.keyword.as_ref() {
+            Ok(M::bind(|| s))
+        } else {
+            let span = inp.span_since(before);
+            inp.add_alt_err(
+                inp.offset().offset,
+                E::Error::expected_found(None, None, span),
+            );
+            Err(())
+        }
+    }
+
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        // Trailing identifier characters are rejected, so only the first character is certain. Mismatches are reported
+        // by the identifier parser rather than in terms of the keyword.
+        let first = C::str_first(self.keyword.as_ref()).into_iter().collect();
+        Summary::token(Some(first)).restricted().relabelled()
+    }
+
+    go_extra!(I::Text);
 }
@@ -0,0 +1,516 @@
+//! Token trees: token streams grouped by their delimiters.
+//!
+//! Many languages have delimiters (such as `(`, `[` and `{`) that must be balanced. Grouping the output of a lexer
+//! into a tree of balanced groups before parsing means that unbalanced delimiters are reported once, by
+//! [`token_trees`], rather than by every parser that expects a closing delimiter. Parsers can then descend into a group
+//! with [`group`], which parses the contents of the group as if they were a complete input, ending at the group's
+//! closing delimiter.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chumsky::{prelude::*, token_tree::{group, token_trees, Group, TokenTree}};
+//! let src = "f(a b) g[c]";
+//! let tokens = src
+//!     .char_indices()
+//!     .filter(|(_, c)| !c.is_whitespace())
+//!     .map(|(i, c)| (c, SimpleSpan::from(i..i + 1)))
+//!     .collect::<Vec<_>>();
+//!
+//! let root = token_trees::<_, extra::Err<Rich<_>>>([('(', ')'), ('[', ']')])
+//!     .parse(tokens.as_slice().spanned((src.len()..src.len()).into()))
+//!     .into_result()
+//!     .unwrap();
+//!
+//! type TreeInput<'a> = &'a Group<char, SimpleSpan>;
+//!
+//! let ident = select_ref! { TokenTree::Token(c) if char::is_alphabetic(*c) => *c };
+//! let call = ident.then(group('(', ident.repeated().collect::<Vec<_>>()));
+//! let index = ident.then(group('[', ident));
+//! let parser = call
+//!     .then(index)
+//!     .map_with_span(|out, span| (out, span))
+//!     .then_ignore(end());
+//!
+//! assert_eq!(
+//!     Parser::<TreeInput, _, extra::Err<Rich<_>>>::parse(&parser, &root).into_result(),
+//!     Ok(((('f', vec!['a', 'b']), ('g', 'c')), SimpleSpan::from(0..11))),
+//! );
+//! ```
+
+use super::*;
+
+/// A token, or a group of token trees surrounded by delimiters.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TokenTree<T, S> {
+    /// A token that is not a delimiter.
+    Token(T),
+    /// A group of token trees surrounded by delimiters.
+    Group(Group<T, S>),
+}
+
+/// A sequence of token trees, usually surrounded by delimiters.
+///
+/// References to groups (`&Group`) implement [`Input`], with tokens of type [`TokenTree`]. The end of the input is at
+/// the start of the group's closing delimiter (or, if the group was never closed, where it was expected).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Group<T, S> {
+    open: Option<(T, S)>,
+    close: Option<(T, S)>,
+    trees: Vec<(TokenTree<T, S>, S)>,
+    eoi: S,
+}
+
+impl<T, S> Group<T, S> {
+    /// Get the opening delimiter of this group, along with its span.
+    ///
+    /// This is `None` for the root group produced by [`token_trees`].
+    pub fn open(&self) -> Option<&(T, S)> {
+        self.open.as_ref()
+    }
+
+    /// Get the closing delimiter of this group, along with its span.
+    ///
+    /// This is `None` for the root group produced by [`token_trees`], and for groups that were never closed.
+    pub fn close(&self) -> Option<&(T, S)> {
+        self.close.as_ref()
+    }
+
+    /// Get the token trees within this group, along with their spans.
+    pub fn trees(&self) -> &[(TokenTree<T, S>, S)] {
+        &self.trees
+    }
+}
+
+impl<T, S> Sealed for &Group<T, S> {}
+impl<'a, T, S> Input<'a> for &'a Group<T, S>
+where
+    T: 'a,
+    S: Span + Clone + 'a,
+{
+    type Offset = usize;
+    type Token = TokenTree<T, S>;
+    type Span = S;
+
+    #[inline(always)]
+    fn start(&self) -> Self::Offset {
+        0
+    }
+
+    type TokenMaybe = &'a TokenTree<T, S>;
+
+    #[inline(always)]
+    unsafe fn next_maybe(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::TokenMaybe>) {
+        self.next_ref(offset)
+    }
+
+    #[inline]
+    unsafe fn span(&self, range: Range<Self::Offset>) -> Self::Span {
+        let start = self
+            .trees
+            .get(range.start)
+            .map_or(self.eoi.start(), |(_, span)| span.start());
+        let end = match range.end.checked_sub(1).and_then(|i| self.trees.get(i)) {
+            Some((_, span)) if range.end > range.start => span.end(),
+            _ => self.eoi.start(),
+        };
+        S::new(self.eoi.context(), start..end)
+    }
+
+    #[inline(always)]
+    fn prev(offs: Self::Offset) -> Self::Offset {
+        offs.saturating_sub(1)
+    }
+}
+
+impl<'a, T, S> ExactSizeInput<'a> for &'a Group<T, S>
+where
+    T: 'a,
+    S: Span + Clone + 'a,
+{
+    #[inline]
+    unsafe fn span_from(&self, range: RangeFrom<Self::Offset>) -> Self::Span {
+        let start = self
+            .trees
+            .get(range.start)
+            .map_or(self.eoi.start(), |(_, span)| span.start());
+        S::new(self.eoi.context(), start..self.eoi.start())
+    }
+}
+
+impl<'a, T, S> ValueInput<'a> for &'a Group<T, S>
+where
+    T: Clone + 'a,
+    S: Span + Clone + 'a,
+{
+    #[inline(always)]
+    unsafe fn next(&self, offset: Self::Offset) -> (Self::Offset, Option<Self::Token>) {
+        let (offset, tree) = self.next_ref(offset);
+        (offset, tree.cloned())
+    }
+}
+
+impl<'a, T, S> BorrowInput<'a> for &'a Group<T, S>
+where
+    T: 'a,
+    S: Span + Clone + 'a,
+{
+    #[inline(always)]
+    unsafe fn next_ref(&self, offset: Self::Offset) -> (Self::Offset, Option<&'a Self::Token>) {
+        match self.trees.get(offset) {
+            Some((tree, _)) => (offset + 1, Some(tree)),
+            None => (offset, None),
+        }
+    }
+}
+
+impl<'a, T, S> SliceInput<'a> for &'a Group<T, S>
+where
+    T: 'a,
+    S: Span + Clone + 'a,
+{
+    type Slice = &'a [(TokenTree<T, S>, S)];
+
+    #[inline(always)]
+    fn slice(&self, range: Range<Self::Offset>) -> Self::Slice {
+        &self.trees[range]
+    }
+
+    #[inline(always)]
+    fn slice_from(&self, from: RangeFrom<Self::Offset>) -> Self::Slice {
+        &self.trees[from]
+    }
+}
+
+/// See [`token_trees`].
+pub struct TokenTrees<T, I, E> {
+    delimiters: Vec<(T, T)>,
+    #[allow(dead_code)]
+    phantom: EmptyPhantom<(I, E)>,
+}
+
+impl<T: Clone, I, E> Clone for TokenTrees<T, I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            delimiters: self.delimiters.clone(),
+            phantom: EmptyPhantom::new(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, I, E> fmt::Debug for TokenTrees<T, I, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TokenTrees").field(&self.delimiters).finish()
+    }
+}
+
+/// A parser that groups its input into a tree of balanced groups, given pairs of opening and closing delimiters.
+///
+/// The output type of this parser is a root [`Group`] (without delimiters) containing the entire input, which can be
+/// parsed as an input in its own right.
+///
+/// Unbalanced delimiters are reported as errors, without causing this parser to fail:
+///
+/// - A group that is still open when the end of the input is reached, or when the closing delimiter of an enclosing
+///   group is found, is reported as an unclosed delimiter (see [`Error::unclosed_delimiter`]) and ends there.
+/// - A closing delimiter that doesn't close any open group is reported as unexpected, and skipped.
+///
+/// # Examples
+///
+/// ```
+/// # use chumsky::{prelude::*, token_tree::{token_trees, TokenTree}};
+/// let trees = token_trees::<_, extra::Err<Rich<_>>>([('(', ')'), ('{', '}')]);
+///
+/// let root = trees.parse("a(b{c})").into_result().unwrap();
+/// assert_eq!(root.trees().len(), 2);
+/// let TokenTree::Group(group) = &root.trees()[1].0 else { panic!() };
+/// assert_eq!(group.open(), Some(&('(', SimpleSpan::from(1..2))));
+/// assert_eq!(group.trees().len(), 2);
+///
+/// let errs = trees.parse("(a}").into_errors();
+/// assert_eq!(errs.len(), 2);
+/// assert_eq!(errs[0].span(), &SimpleSpan::from(2..3)); // Unexpected '}'
+/// assert_eq!(errs[1].to_string(), "unclosed delimiter"); // Unclosed '('
+/// assert_eq!(errs[1].span(), &SimpleSpan::from(0..1));
+/// ```
+pub fn token_trees<'a, I, E>(
+    delimiters: impl IntoIterator<Item = (I::Token, I::Token)>,
+) -> TokenTrees<I::Token, I, E>
+where
+    I: ValueInput<'a>,
+    E: ParserExtra<'a, I>,
+{
+    TokenTrees {
+        delimiters: delimiters.into_iter().collect(),
+        phantom: EmptyPhantom::new(),
+    }
+}
+
+// A group that is still being built
+struct Frame<T, S> {
+    // The opening delimiter and the index of its delimiter pair
+    open: Option<(T, S, usize)>,
+    trees: Vec<(TokenTree<T, S>, S)>,
+}
+
+impl<T, S: Span + Clone> Frame<T, S>
+where
+    S::Offset: Clone,
+{
+    // Finish the group, ending at the given span (the closing delimiter, or where it was expected)
+    fn finish(self, close: Option<(T, S)>, eoi: S) -> (TokenTree<T, S>, S) {
+        let (open, open_span, _) = self.open.expect("root group cannot be finished");
+        let end = match &close {
+            Some((_, span)) => span.end(),
+            None => self
+                .trees
+                .last()
+                .map_or(open_span.end(), |(_, span)| span.end()),
+        };
+        let span = S::new(open_span.context(), open_span.start()..end);
+        let group = Group {
+            open: Some((open, open_span)),
+            close,
+            trees: self.trees,
+            eoi,
+        };
+        (TokenTree::Group(group), span)
+    }
+}
+
+impl<'a, I, E> TokenTrees<I::Token, I, E>
+where
+    I: ValueInput<'a>,
+    I::Token: PartialEq + Clone,
+    I::Span: Clone,
+    <I::Span as Span>::Offset: Clone,
+    E: ParserExtra<'a, I>,
+{
+    // Report that the innermost group was not closed, and add it to its parent
+    fn unclosed<'parse>(
+        &self,
+        inp: &mut InputRef<'a, 'parse, I, E>,
+        stack: &mut Vec<Frame<I::Token, I::Span>>,
+        at: input::Offset<'a, 'parse, I>,
+        found: Option<I::Token>,
+        span: I::Span,
+    ) -> Result<(), ()> {
+        let frame = stack.pop().expect("root group cannot be unclosed");
+        let (_, open_span, delim) = frame.open.as_ref().expect("root group cannot be unclosed");
+        if !inp.errors.begin_recovery(at.offset) {
+            return Err(());
+        }
+        let expected = Some(Some(MaybeRef::Val(self.delimiters[*delim].1.clone())));
+        let err = E::Error::expected_found(expected, found.map(MaybeRef::Val), span.clone())
+            .unclosed_delimiter(open_span.clone());
+        inp.emit(at.offset, err);
+        let eoi = I::Span::new(span.context(), span.start()..span.start());
+        let tree = frame.finish(None, eoi);
+        stack.last_mut().expect("root group").trees.push(tree);
+        Ok(())
+    }
+}
+
+impl<'a, I, E> ParserSealed<'a, I, Group<I::Token, I::Span>, E> for TokenTrees<I::Token, I, E>
+where
+    I: ValueInput<'a>,
+    I::Token: PartialEq + Clone,
+    I::Span: Clone,
+    <I::Span as Span>::Offset: Clone,
+    E: ParserExtra<'a, I>,
+{
+    #[inline]
+    fn go<M: Mode>(
+        &self,
+        inp: &mut InputRef<'a, '_, I, E>,
+    ) -> PResult<M, Group<I::Token, I::Span>> {
+        let mut stack = vec![Frame {
+            open: None,
+            trees: Vec::new(),
+        }];
+        loop {
+            let before = inp.offset();
+            let tok = match inp.next_inner() {
+                (_, Some(tok)) => tok,
+                (_, None) => break,
+            };
+            let span = inp.span_since(before);
+
+            if let Some(delim) = self.delimiters.iter().position(|(open, _)| *open == tok) {
+                stack.push(Frame {
+                    open: Some((tok, span, delim)),
+                    trees: Vec::new(),
+                });
+            } else if let Some(delim) = self.delimiters.iter().position(|(_, close)| *close == tok)
+            {
+                let closes = stack
+                    .iter()
+                    .rposition(|frame| matches!(frame.open, Some((_, _, d)) if d == delim));
+                match closes {
+                    Some(idx) => {
+                        // Groups opened within the group being closed were never closed themselves
+                        while stack.len() > idx + 1 {
+                            self.unclosed(
+                                inp,
+                                &mut stack,
+                                before,
+                                Some(tok.clone()),
+                                span.clone(),
+                            )?;
+                        }
+                        let frame = stack.pop().expect("group being closed");
+                        let tree = frame.finish(Some((tok, span.clone())), span);
+                        stack.last_mut().expect("root group").trees.push(tree);
+                    }
+                    None => {
+                        if !inp.errors.begin_recovery(before.offset) {
+                            return Err(());
+                        }
+                        let expected = stack.last().and_then(|frame| frame.open.as_ref()).map(
+                            |(_, _, delim)| Some(MaybeRef::Val(self.delimiters[*delim].1.clone())),
+                        );
+                        let err =
+                            E::Error::expected_found(expected, Some(MaybeRef::Val(tok)), span);
+                        inp.emit(before.offset, err);
+                        inp.mark_recovered(before.offset);
+                    }
+                }
+            } else {
+                stack
+                    .last_mut()
+                    .expect("root group")
+                    .trees
+                    .push((TokenTree::Token(tok), span));
+            }
+        }
+
+        let end = inp.offset();
+        let eoi = inp.span_since(end);
+        while stack.len() > 1 {
+            self.unclosed(inp, &mut stack, end, None, eoi.clone())?;
+        }
+        let root = stack.pop().expect("root group");
+        Ok(M::bind(|| Group {
+            open: None,
+            close: None,
+            trees: root.trees,
+            eoi,
+        }))
+    }
+
+    fn summarize(&self, _cx: &mut Analyzer<I::Token>) -> Summary<I::Token>
+    where
+        I::Token: Clone + PartialEq,
+    {
+        // Any sequence of tokens can be grouped, including an empty one
+        Summary::token(None).repeated(0).restricted()
+    }
+
+    go_extra!(Group<I::Token, I::Span>);
+}
+
+/// Parse the contents of a group delimited by the given opening delimiter with a parser, as if they were a complete
+/// input. The end of the group's input is at its closing delimiter, so errors at the end of the group point there.
+///
+/// This is [`Parser::nested_in`] applied to a parser that selects a [`TokenTree::Group`] with the given opening
+/// delimiter.
+///
+/// The output type of this parser is `O`, the output of the given parser.
+pub fn group<'a, T, S, O, E, P>(
+    delimiter: T,
+    parser: P,
+) -> impl Parser<'a, &'a Group<T, S>, O, E> + Clone
+where
+    T: PartialEq + Clone + 'a,
+    S: Span + Clone + 'a,
+    E: ParserExtra<'a, &'a Group<T, S>>,
+    P: Parser<'a, &'a Group<T, S>, O, E> + Clone,
+{
+    parser.nested_in(primitive::select_ref(move |tree, _| match tree {
+        TokenTree::Group(group)
+            if group.open.as_ref().map(|(open, _)| open) == Some(&delimiter) =>
+        {
+            Some(group)
+        }
+        _ => None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{group, *};
+    use crate::prelude::*;
+
+    type TreeInput<'a> = &'a Group<char, SimpleSpan>;
+    type TreeError<'a> = Rich<'a, TokenTree<char, SimpleSpan>>;
+
+    fn tokens(src: &str) -> Vec<(char, SimpleSpan)> {
+        src.char_indices()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(|(i, c)| (c, SimpleSpan::from(i..i + 1)))
+            .collect()
+    }
+
+    fn trees<'a>(
+        src: &str,
+        toks: &'a [(char, SimpleSpan)],
+    ) -> (Group<char, SimpleSpan>, Vec<Rich<'a, char>>) {
+        let (root, errs) = token_trees::<_, extra::Err<Rich<_>>>([('(', ')'), ('[', ']')])
+            .parse(toks.spanned((src.len()..src.len()).into()))
+            .into_output_errors();
+        (root.unwrap(), errs)
+    }
+
+    #[test]
+    fn unbalanced() {
+        let src = "a([b)c]";
+        let toks = tokens(src);
+        let (root, errs) = trees(src, &toks);
+        // '[' is closed by ')', and the ']' that follows is unexpected
+        assert_eq!(
+            errs.iter().map(|e| *e.span()).collect::<Vec<_>>(),
+            [SimpleSpan::from(2..3), SimpleSpan::from(6..7)],
+        );
+        assert_eq!(errs[0].to_string(), "unclosed delimiter");
+        assert_eq!(root.trees().len(), 3);
+        let TokenTree::Group(paren) = &root.trees()[1].0 else {
+            panic!()
+        };
+        assert_eq!(root.trees()[1].1, SimpleSpan::from(1..5));
+        assert_eq!(paren.close(), Some(&(')', SimpleSpan::from(4..5))));
+        let TokenTree::Group(bracket) = &paren.trees()[0].0 else {
+            panic!()
+        };
+        assert_eq!(paren.trees()[0].1, SimpleSpan::from(2..4));
+        assert_eq!(bracket.close(), None);
+    }
+
+    fn call_then_index<'a>(
+    ) -> impl Parser<'a, TreeInput<'a>, ((char, Vec<char>), (char, char)), extra::Err<TreeError<'a>>>
+    {
+        let ident = select_ref! { TokenTree::Token(c) if char::is_alphabetic(*c) => *c };
+        let call = ident.then(group('(', ident.repeated().collect::<Vec<_>>()));
+        let index = ident.then(group('[', ident));
+        call.then(index).then_ignore(end())
+    }
+
+    #[test]
+    fn group_errors() {
+        let src = "f(a 1 b) g[]";
+        let toks = tokens(src);
+        let (root, _) = trees(src, &toks);
+        let errs = call_then_index().parse(&root).into_errors();
+        // Spans within groups refer to the original input
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(4..5));
+
+        // The end of a group's input is at its closing delimiter
+        let src = "f() g[]";
+        let toks = tokens(src);
+        let (root, _) = trees(src, &toks);
+        let errs = call_then_index().parse(&root).into_errors();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].span(), &SimpleSpan::from(6..6));
+        assert_eq!(errs[0].found(), None);
+    }
+}